@@ -0,0 +1,223 @@
+//! RPC interface for the chainbridge pallet.
+//!
+//! Node builders can mount `ChainBridge` to let relayers query the bridge state without
+//! decoding storage of the pallet.
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use chainbridge::{Bitfield, ChainId, DepositNonce, ProposalStatus, ProposalVotes, ResourceInfo};
+pub use chainbridge_runtime_api::ChainBridgeApi as ChainBridgeRuntimeApi;
+use codec::{Codec, Decode};
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H256};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+mod tests;
+
+/// A registered resource along with its hex encoded ID
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Resource {
+    pub id: H256,
+    pub info: ResourceInfo,
+}
+
+/// The votes on a proposal by relayer account.
+///
+/// Votes in slots that aren't assigned to a relayer anymore are omitted.
+#[derive(PartialEq, Eq, Clone, Debug, Serialize, Deserialize)]
+pub struct Votes<AccountId, BlockNumber> {
+    pub votes_for: Vec<AccountId>,
+    pub votes_against: Vec<AccountId>,
+    pub status: ProposalStatus,
+    pub expiry: BlockNumber,
+}
+
+impl<AccountId: Clone, BlockNumber> Votes<AccountId, BlockNumber> {
+    /// Resolves the slots of the votes with the current relayers
+    pub fn new(votes: ProposalVotes<BlockNumber>, relayers: &[(AccountId, u32)]) -> Self {
+        let accounts = |slots: &Bitfield| {
+            slots
+                .iter()
+                .filter_map(|slot| {
+                    relayers
+                        .iter()
+                        .find(|(_, s)| *s == slot)
+                        .map(|(relayer, _)| relayer.clone())
+                })
+                .collect()
+        };
+        Votes {
+            votes_for: accounts(&votes.votes_for),
+            votes_against: accounts(&votes.votes_against),
+            status: votes.status,
+            expiry: votes.expiry,
+        }
+    }
+}
+
+#[rpc]
+pub trait ChainBridgeApi<BlockHash, AccountId, BlockNumber, Balance> {
+    /// Returns the current relayer set
+    #[rpc(name = "chainBridge_relayers")]
+    fn relayers(&self, at: Option<BlockHash>) -> Result<Vec<AccountId>>;
+
+    /// Returns the votes of the proposal for a SCALE encoded call from a source chain
+    #[rpc(name = "chainBridge_proposal")]
+    fn proposal(
+        &self,
+        src_id: ChainId,
+        nonce: DepositNonce,
+        call: Bytes,
+        at: Option<BlockHash>,
+    ) -> Result<Option<Votes<AccountId, BlockNumber>>>;
+
+    /// Returns the deposit nonce of a whitelisted chain
+    #[rpc(name = "chainBridge_chainNonce")]
+    fn chain_nonce(&self, id: ChainId, at: Option<BlockHash>) -> Result<Option<DepositNonce>>;
+
+    /// Returns all registered resources
+    #[rpc(name = "chainBridge_resources")]
+    fn resources(&self, at: Option<BlockHash>) -> Result<Vec<Resource>>;
+
+    /// Returns the fee charged for a transfer of a resource to a chain
+    #[rpc(name = "chainBridge_fee")]
+    fn fee(&self, dest_id: ChainId, resource_id: H256, at: Option<BlockHash>) -> Result<Balance>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+    /// The call could not be decoded.
+    DecodeError,
+    /// The call to the runtime failed.
+    RuntimeError,
+}
+
+impl From<Error> for i64 {
+    fn from(e: Error) -> i64 {
+        match e {
+            Error::RuntimeError => 1,
+            Error::DecodeError => 2,
+        }
+    }
+}
+
+/// Implements the `ChainBridgeApi` RPC trait for querying the chainbridge pallet.
+pub struct ChainBridge<C, Block, Proposal> {
+    client: Arc<C>,
+    _marker: PhantomData<(Block, Proposal)>,
+}
+
+impl<C, Block, Proposal> ChainBridge<C, Block, Proposal> {
+    /// Creates a new instance of the ChainBridge RPC helper.
+    pub fn new(client: Arc<C>) -> Self {
+        Self {
+            client,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, Block, Proposal> ChainBridge<C, Block, Proposal>
+where
+    Block: BlockT,
+    C: HeaderBackend<Block>,
+{
+    /// Returns the requested block, or the best block if none is given
+    fn block_id(&self, at: Option<<Block as BlockT>::Hash>) -> BlockId<Block> {
+        // If the block hash is not supplied assume the best block.
+        BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash))
+    }
+}
+
+/// Converts a runtime API failure into an RPC error
+fn runtime_error(message: &str, e: impl std::fmt::Debug) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(Error::RuntimeError.into()),
+        message: message.into(),
+        data: Some(format!("{:?}", e).into()),
+    }
+}
+
+impl<C, Block, AccountId, BlockNumber, Balance, Proposal>
+    ChainBridgeApi<<Block as BlockT>::Hash, AccountId, BlockNumber, Balance>
+    for ChainBridge<C, Block, Proposal>
+where
+    Block: BlockT,
+    C: 'static + Send + Sync + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: ChainBridgeRuntimeApi<Block, AccountId, BlockNumber, Balance, Proposal>,
+    AccountId: Codec + Clone,
+    BlockNumber: Codec,
+    Balance: Codec,
+    Proposal: Codec,
+{
+    fn relayers(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<AccountId>> {
+        let api = self.client.runtime_api();
+        api.relayers(&self.block_id(at))
+            .map_err(|e| runtime_error("Unable to query relayers.", e))
+    }
+
+    fn proposal(
+        &self,
+        src_id: ChainId,
+        nonce: DepositNonce,
+        call: Bytes,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<Votes<AccountId, BlockNumber>>> {
+        let call: Proposal = Decode::decode(&mut &*call).map_err(|e| RpcError {
+            code: ErrorCode::ServerError(Error::DecodeError.into()),
+            message: "Unable to decode the call.".into(),
+            data: Some(format!("{:?}", e).into()),
+        })?;
+
+        let api = self.client.runtime_api();
+        let at = self.block_id(at);
+        let votes = api
+            .proposal_votes(&at, src_id, nonce, call)
+            .map_err(|e| runtime_error("Unable to query proposal.", e))?;
+        let relayers = api
+            .relayer_slots(&at)
+            .map_err(|e| runtime_error("Unable to query relayers.", e))?;
+        Ok(votes.map(|votes| Votes::new(votes, &relayers)))
+    }
+
+    fn chain_nonce(
+        &self,
+        id: ChainId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<DepositNonce>> {
+        let api = self.client.runtime_api();
+        api.chain_nonce(&self.block_id(at), id)
+            .map_err(|e| runtime_error("Unable to query chain nonce.", e))
+    }
+
+    fn resources(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<Resource>> {
+        let api = self.client.runtime_api();
+        let resources = api
+            .resources(&self.block_id(at))
+            .map_err(|e| runtime_error("Unable to query resources.", e))?;
+        Ok(resources
+            .into_iter()
+            .map(|(id, info)| Resource {
+                id: H256::from(id),
+                info,
+            })
+            .collect())
+    }
+
+    fn fee(
+        &self,
+        dest_id: ChainId,
+        resource_id: H256,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Balance> {
+        let api = self.client.runtime_api();
+        api.bridge_fee(&self.block_id(at), dest_id, resource_id.into())
+            .map_err(|e| runtime_error("Unable to query bridge fee.", e))
+    }
+}
@@ -0,0 +1,234 @@
+#![cfg(test)]
+
+use super::*;
+
+use chainbridge::{derive_resource_id, ProposalStatus, ResourceId, ResourceKind};
+use codec::Encode;
+use jsonrpc_core::IoHandler;
+use sp_api::ApiRef;
+use sp_blockchain::{BlockStatus, Info};
+use substrate_test_runtime_client::runtime::Block;
+
+type AccountId = u64;
+type BlockNumber = u64;
+type Balance = u64;
+type Proposal = u32;
+
+/// The only proposal known to the mock runtime
+const PROPOSAL: Proposal = 7;
+
+#[derive(Default, Clone)]
+struct TestApi {
+    relayers: Vec<AccountId>,
+    resources: Vec<(ResourceId, ResourceInfo)>,
+}
+
+struct RuntimeApi {
+    inner: TestApi,
+}
+
+impl ProvideRuntimeApi<Block> for TestApi {
+    type Api = RuntimeApi;
+
+    fn runtime_api<'a>(&'a self) -> ApiRef<'a, Self::Api> {
+        RuntimeApi {
+            inner: self.clone(),
+        }
+        .into()
+    }
+}
+
+impl HeaderBackend<Block> for TestApi {
+    fn header(
+        &self,
+        _: BlockId<Block>,
+    ) -> sp_blockchain::Result<Option<<Block as BlockT>::Header>> {
+        Ok(None)
+    }
+
+    fn info(&self) -> Info<Block> {
+        Info {
+            best_hash: Default::default(),
+            best_number: 0,
+            genesis_hash: Default::default(),
+            finalized_hash: Default::default(),
+            finalized_number: 0,
+            number_leaves: 0,
+        }
+    }
+
+    fn status(&self, _: BlockId<Block>) -> sp_blockchain::Result<BlockStatus> {
+        Ok(BlockStatus::Unknown)
+    }
+
+    fn number(
+        &self,
+        _: <Block as BlockT>::Hash,
+    ) -> sp_blockchain::Result<
+        Option<<<Block as BlockT>::Header as sp_runtime::traits::Header>::Number>,
+    > {
+        Ok(None)
+    }
+
+    fn hash(
+        &self,
+        _: <<Block as BlockT>::Header as sp_runtime::traits::Header>::Number,
+    ) -> sp_blockchain::Result<Option<<Block as BlockT>::Hash>> {
+        Ok(None)
+    }
+}
+
+sp_api::mock_impl_runtime_apis! {
+    impl ChainBridgeRuntimeApi<Block, AccountId, BlockNumber, Balance, Proposal> for RuntimeApi {
+        fn relayers(&self) -> Vec<AccountId> {
+            self.inner.relayers.clone()
+        }
+
+        fn relayer_slots(&self) -> Vec<(AccountId, u32)> {
+            self.inner.relayers.iter().cloned().zip(0..).collect()
+        }
+
+        fn relayer_threshold() -> u32 {
+            2
+        }
+
+        fn whitelisted_chains() -> Vec<(ChainId, DepositNonce)> {
+            vec![(1, 5)]
+        }
+
+        fn chain_nonce(id: ChainId) -> Option<DepositNonce> {
+            if id == 1 { Some(5) } else { None }
+        }
+
+        fn resources(&self) -> Vec<(ResourceId, ResourceInfo)> {
+            self.inner.resources.clone()
+        }
+
+        fn bridge_fee(dest_id: ChainId, r_id: ResourceId) -> Balance {
+            if dest_id == 1 && r_id == derive_resource_id(1, b"remark") { 10 } else { 0 }
+        }
+
+        fn proposal_votes(
+            &self,
+            src_id: ChainId,
+            nonce: DepositNonce,
+            call: Proposal,
+        ) -> Option<ProposalVotes<BlockNumber>> {
+            if src_id == 1 && nonce == 1 && call == PROPOSAL {
+                Some(ProposalVotes {
+                    // Slot 2 is not assigned to a relayer
+                    votes_for: vec![0, 1, 2].into_iter().collect(),
+                    votes_against: Bitfield::default(),
+                    status: ProposalStatus::Executed,
+                    expiry: 10,
+                })
+            } else {
+                None
+            }
+        }
+
+        fn can_vote(
+            _relayer: AccountId,
+            _src_id: ChainId,
+            _nonce: DepositNonce,
+            _call: Proposal,
+        ) -> bool {
+            false
+        }
+    }
+}
+
+fn test_io() -> IoHandler {
+    let r_id = derive_resource_id(1, b"remark");
+    let client = TestApi {
+        relayers: vec![1, 2],
+        resources: vec![(
+            r_id,
+            ResourceInfo::new(ResourceKind::Generic, b"System.remark".to_vec()),
+        )],
+    };
+
+    let mut io = IoHandler::new();
+    io.extend_with(ChainBridgeApi::to_delegate(
+        ChainBridge::<_, Block, Proposal>::new(Arc::new(client)),
+    ));
+    io
+}
+
+fn request(method: &str, params: &str) -> String {
+    let request = format!(
+        r#"{{"jsonrpc":"2.0","method":"{}","params":{},"id":1}}"#,
+        method, params
+    );
+    test_io().handle_request_sync(&request).unwrap()
+}
+
+fn result(response: &str) -> serde_json::Value {
+    let response: serde_json::Value = serde_json::from_str(response).unwrap();
+    response["result"].clone()
+}
+
+#[test]
+fn relayers() {
+    let response = request("chainBridge_relayers", "[]");
+    assert_eq!(result(&response), serde_json::json!([1, 2]));
+}
+
+#[test]
+fn proposal() {
+    let call = format!("0x{}", hex(&PROPOSAL.encode()));
+    let response = request("chainBridge_proposal", &format!(r#"[1, 1, "{}"]"#, call));
+    assert_eq!(
+        result(&response),
+        serde_json::json!({
+            "votes_for": [1, 2],
+            "votes_against": [],
+            "status": "Executed",
+            "expiry": 10,
+        })
+    );
+
+    // Unknown proposals
+    let response = request("chainBridge_proposal", &format!(r#"[1, 2, "{}"]"#, call));
+    assert_eq!(result(&response), serde_json::Value::Null);
+
+    // Calls that can't be decoded
+    let response = request("chainBridge_proposal", r#"[1, 1, "0x07"]"#);
+    let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(response["error"]["code"], 2);
+}
+
+#[test]
+fn chain_nonce() {
+    let response = request("chainBridge_chainNonce", "[1]");
+    assert_eq!(result(&response), serde_json::json!(5));
+
+    let response = request("chainBridge_chainNonce", "[2]");
+    assert_eq!(result(&response), serde_json::Value::Null);
+}
+
+#[test]
+fn resources() {
+    let response = request("chainBridge_resources", "[]");
+    let resources = result(&response);
+    assert_eq!(
+        resources[0]["id"],
+        format!("0x{}", hex(&derive_resource_id(1, b"remark")))
+    );
+    assert_eq!(resources[0]["info"]["kind"], "Generic");
+    assert_eq!(resources[0]["info"]["enabled"], true);
+}
+
+#[test]
+fn fee() {
+    let r_id = format!("0x{}", hex(&derive_resource_id(1, b"remark")));
+    let response = request("chainBridge_fee", &format!(r#"[1, "{}"]"#, r_id));
+    assert_eq!(result(&response), serde_json::json!(10));
+
+    let response = request("chainBridge_fee", &format!(r#"[2, "{}"]"#, r_id));
+    assert_eq!(result(&response), serde_json::json!(0));
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
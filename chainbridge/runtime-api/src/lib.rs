@@ -0,0 +1,50 @@
+//! Runtime API for querying the state of the chainbridge pallet.
+//!
+//! Relayers and dashboards can use it instead of decoding storage of the pallet.
+
+// Ensure we're `no_std` when compiling for Wasm.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use chainbridge::{ChainId, DepositNonce, ProposalVotes, ResourceId, ResourceInfo};
+use codec::Codec;
+use sp_std::prelude::*;
+
+sp_api::decl_runtime_apis! {
+    pub trait ChainBridgeApi<AccountId, BlockNumber, Balance, Proposal> where
+        AccountId: Codec,
+        BlockNumber: Codec,
+        Balance: Codec,
+        Proposal: Codec,
+    {
+        /// Returns the current relayer set
+        fn relayers() -> Vec<AccountId>;
+
+        /// Returns the current relayers along with the slots their votes are recorded in
+        fn relayer_slots() -> Vec<(AccountId, u32)>;
+
+        /// Returns the number of votes currently required for a proposal to execute
+        fn relayer_threshold() -> u32;
+
+        /// Returns all whitelisted chains and their deposit nonce
+        fn whitelisted_chains() -> Vec<(ChainId, DepositNonce)>;
+
+        /// Returns the deposit nonce of a whitelisted chain
+        fn chain_nonce(id: ChainId) -> Option<DepositNonce>;
+
+        /// Returns all registered resources
+        fn resources() -> Vec<(ResourceId, ResourceInfo)>;
+
+        /// Returns the fee charged for a transfer of a resource to a chain
+        fn bridge_fee(dest_id: ChainId, r_id: ResourceId) -> Balance;
+
+        /// Returns the votes of the proposal for a call from a source chain
+        fn proposal_votes(
+            src_id: ChainId,
+            nonce: DepositNonce,
+            call: Proposal,
+        ) -> Option<ProposalVotes<BlockNumber>>;
+
+        /// Returns true if the relayer may currently vote on the proposal
+        fn can_vote(relayer: AccountId, src_id: ChainId, nonce: DepositNonce, call: Proposal) -> bool;
+    }
+}
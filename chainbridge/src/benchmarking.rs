@@ -0,0 +1,544 @@
+//! Benchmarks for chainbridge
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_core::sr25519;
+use sp_runtime::KeyTypeId;
+
+const SEED: u32 = 0;
+const MAX_PRUNED: u32 = 100;
+const NONCE: DepositNonce = 1;
+const KEY_TYPE: KeyTypeId = KeyTypeId(*b"brdg");
+
+/// Gives an account enough funds to reserve the relayer bond
+fn endow<T: Config>(who: &T::AccountId) {
+    let balance = T::RelayerBond::get().saturating_add(T::Currency::minimum_balance());
+    T::Currency::make_free_balance_be(who, balance);
+}
+
+/// Registers `r` relayers and returns their accounts
+fn setup_relayers<T: Config>(r: u32) -> Result<Vec<T::AccountId>, &'static str> {
+    let mut relayers = vec![];
+    for i in 0..r {
+        let relayer: T::AccountId = account("relayer", i, SEED);
+        endow::<T>(&relayer);
+        Module::<T>::register_relayer(relayer.clone())?;
+        relayers.push(relayer);
+    }
+    Ok(relayers)
+}
+
+/// Whitelists a source chain and registers a resource for it
+fn setup_bridge<T: Config>() -> Result<(ChainId, ResourceId), &'static str> {
+    let src_id = T::ChainId::get().wrapping_add(1);
+    let r_id = derive_resource_id(src_id, b"remark");
+    Module::<T>::whitelist(src_id)?;
+    let info = ResourceInfo::new(ResourceKind::Generic, b"System.remark".to_vec());
+    Module::<T>::register_resource(r_id, info)?;
+    Ok((src_id, r_id))
+}
+
+/// Deposit data for the generic resource registered by `setup_bridge`
+fn make_deposit() -> Vec<u8> {
+    Deposit::Generic { metadata: vec![] }.to_data()
+}
+
+fn make_proposal<T: Config>() -> T::Proposal
+where
+    T::Proposal: From<frame_system::Call<T>>,
+{
+    frame_system::Call::<T>::remark(vec![]).into()
+}
+
+benchmarks! {
+    where_clause { where T::Proposal: From<frame_system::Call<T>> }
+
+    set_threshold {
+        let threshold = 3;
+        setup_relayers::<T>(threshold)?;
+    }: _(RawOrigin::Root, threshold)
+    verify {
+        assert_eq!(Module::<T>::relayer_threshold(), threshold);
+    }
+
+    set_threshold_ratio {
+        setup_relayers::<T>(3)?;
+        let ratio = Some(Perbill::from_percent(67));
+    }: _(RawOrigin::Root, ratio)
+    verify {
+        assert_eq!(Module::<T>::relayer_threshold_ratio(), ratio);
+    }
+
+    set_resource {
+        let r_id = derive_resource_id(1, b"remark");
+        let info = ResourceInfo {
+            allowed_chains: vec![1],
+            ..ResourceInfo::new(ResourceKind::Generic, b"System.remark".to_vec())
+        };
+    }: _(RawOrigin::Root, r_id, info.clone())
+    verify {
+        assert_eq!(Module::<T>::resources(r_id), Some(info));
+    }
+
+    remove_resource {
+        let (_, r_id) = setup_bridge::<T>()?;
+    }: _(RawOrigin::Root, r_id)
+    verify {
+        assert!(!Module::<T>::resource_exists(r_id));
+    }
+
+    whitelist_chain {
+        let id = T::ChainId::get().wrapping_add(1);
+    }: _(RawOrigin::Root, id)
+    verify {
+        assert!(Module::<T>::chain_whitelisted(id));
+    }
+
+    remove_chain {
+        let (src_id, _) = setup_bridge::<T>()?;
+    }: _(RawOrigin::Root, src_id)
+    verify {
+        assert!(!Module::<T>::chain_whitelisted(src_id));
+    }
+
+    set_chain_direction {
+        let (src_id, _) = setup_bridge::<T>()?;
+    }: _(RawOrigin::Root, src_id, ChainDirection::Inbound)
+    verify {
+        assert_eq!(Module::<T>::chain_direction(src_id), ChainDirection::Inbound);
+    }
+
+    set_fee {
+        let (dest_id, r_id) = setup_bridge::<T>()?;
+        let fee = T::FeeCurrency::minimum_balance();
+    }: _(RawOrigin::Root, dest_id, r_id, fee)
+    verify {
+        assert_eq!(Module::<T>::bridge_fee((dest_id, r_id)), fee);
+    }
+
+    // The fee account is emptied into a new account.
+    withdraw_fees {
+        let fees = Module::<T>::fees_account_id();
+        let amount = T::FeeCurrency::minimum_balance() * 100u32.into();
+        T::FeeCurrency::make_free_balance_be(&fees, amount);
+        let to: T::AccountId = account("recipient", 0, SEED);
+    }: _(RawOrigin::Root, to.clone(), amount)
+    verify {
+        assert_eq!(T::FeeCurrency::free_balance(&to), amount);
+    }
+
+    // The relayer set is one below its limit.
+    // The slots below the last of `m` are still cooling down, so the lookup reads all of them
+    // before finding a free slot.
+    add_relayer {
+        let m in 1 .. T::MaxRelayers::get();
+
+        let now = frame_system::Pallet::<T>::block_number();
+        for slot in 0..m - 1 {
+            <FreedSlots<T>>::insert(slot, now);
+        }
+        let relayer: T::AccountId = account("relayer", T::MaxRelayers::get(), SEED);
+        endow::<T>(&relayer);
+    }: _(RawOrigin::Root, relayer.clone())
+    verify {
+        assert_eq!(Module::<T>::relayer_slot(&relayer), Some(m - 1));
+    }
+
+    // The relayer set is left with one relayer to meet the threshold.
+    remove_relayer {
+        let relayer = setup_relayers::<T>(2)?.remove(0);
+    }: _(RawOrigin::Root, relayer.clone())
+    verify {
+        assert!(!Module::<T>::is_relayer(&relayer));
+    }
+
+    set_relayer_key {
+        let relayer = setup_relayers::<T>(1)?.remove(0);
+        let key = MultiSigner::Sr25519(sr25519::Public::from_raw([1; 32]));
+    }: _(RawOrigin::Signed(relayer.clone()), key.clone())
+    verify {
+        assert_eq!(Module::<T>::relayer_key(&relayer), Some(key));
+    }
+
+    withdraw_unbonded {
+        let relayer = setup_relayers::<T>(2)?.remove(0);
+        Module::<T>::unregister_relayer(relayer.clone())?;
+        let now = frame_system::Pallet::<T>::block_number();
+        frame_system::Pallet::<T>::set_block_number(now + Module::<T>::unbonding_period());
+    }: _(RawOrigin::Signed(relayer.clone()))
+    verify {
+        assert!(Module::<T>::bond(&relayer).is_none());
+    }
+
+    // The relayer voted for a conflicting proposal before the proposal of the deposit was
+    // executed.
+    report_misbehaviour {
+        let (src_id, _) = setup_bridge::<T>()?;
+        let relayers = setup_relayers::<T>(2)?;
+        Module::<T>::set_relayer_threshold(2)?;
+        let conflicting: T::Proposal = frame_system::Call::<T>::remark(vec![1]).into();
+        Module::<T>::vote_for(relayers[0].clone(), NONCE, src_id, Box::new(conflicting.clone()))?;
+        let proposal = make_proposal::<T>();
+        for relayer in &relayers {
+            Module::<T>::vote_for(relayer.clone(), NONCE, src_id, Box::new(proposal.clone()))?;
+        }
+        let prop_hash = Module::<T>::proposal_hash(&conflicting);
+        let caller: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Signed(caller), relayers[0].clone(), src_id, NONCE, prop_hash)
+    verify {
+        assert!(Module::<T>::bond(&relayers[0]).unwrap().amount.is_zero());
+    }
+
+    claim_rewards {
+        let relayer = setup_relayers::<T>(1)?.remove(0);
+        RewardPoints::<T>::insert(&relayer, 1);
+        TotalRewardPoints::put(2);
+        let pot = Module::<T>::rewards_account_id();
+        T::Currency::make_free_balance_be(&pot, T::Currency::minimum_balance() * 100u32.into());
+    }: _(RawOrigin::Signed(relayer.clone()))
+    verify {
+        assert_eq!(Module::<T>::reward_points(&relayer), 0);
+    }
+
+    pause_bridge {
+    }: _(RawOrigin::Root)
+    verify {
+        assert!(Module::<T>::bridge_paused());
+    }
+
+    unpause_bridge {
+        BridgePaused::put(true);
+    }: _(RawOrigin::Root)
+    verify {
+        assert!(!Module::<T>::bridge_paused());
+    }
+
+    pause_chain {
+        let (src_id, _) = setup_bridge::<T>()?;
+    }: _(RawOrigin::Root, src_id)
+    verify {
+        assert!(Module::<T>::chain_paused(src_id));
+    }
+
+    unpause_chain {
+        let (src_id, _) = setup_bridge::<T>()?;
+        PausedChains::insert(src_id, true);
+    }: _(RawOrigin::Root, src_id)
+    verify {
+        assert!(!Module::<T>::chain_paused(src_id));
+    }
+
+    // Every legacy entry is removed, the last removal clears the flag.
+    purge_legacy_votes {
+        let v in 0 .. MAX_PRUNED;
+
+        let (src_id, _) = setup_bridge::<T>()?;
+        for nonce in 0..v {
+            let votes = migrations::deprecated::ProposalVotes {
+                votes_for: vec![],
+                votes_against: vec![],
+                status: ProposalStatus::Approved,
+                expiry: T::BlockNumber::zero(),
+            };
+            <Votes<T>>::insert(src_id, (nonce as DepositNonce, make_proposal::<T>()), votes);
+        }
+        LegacyVotesRemain::put(true);
+    }: _(RawOrigin::Root, v)
+    verify {
+        assert!(!Module::<T>::legacy_votes_remain());
+    }
+
+    // All but the last relayer have voted in favour, the last vote reaches the threshold
+    // and executes the proposal.
+    acknowledge_proposal {
+        let r in 1 .. T::MaxRelayers::get();
+
+        let (src_id, r_id) = setup_bridge::<T>()?;
+        let mut relayers = setup_relayers::<T>(r)?;
+        Module::<T>::set_relayer_threshold(r)?;
+        let proposal = make_proposal::<T>();
+        let caller = relayers.pop().expect("at least one relayer");
+        for relayer in relayers {
+            Module::<T>::vote_for(relayer, NONCE, src_id, Box::new(proposal.clone()))?;
+        }
+    }: _(RawOrigin::Signed(caller), NONCE, src_id, r_id, Box::new(proposal.clone()))
+    verify {
+        let prop_hash = Module::<T>::proposal_hash(&proposal);
+        let votes = Module::<T>::votes(src_id, (NONCE, prop_hash)).unwrap();
+        assert_eq!(votes.status, ProposalStatus::Executed);
+    }
+
+    // All but the last relayer have voted in favour, the last vote against rejects the proposal.
+    reject_proposal {
+        let r in 1 .. T::MaxRelayers::get();
+
+        let (src_id, r_id) = setup_bridge::<T>()?;
+        let mut relayers = setup_relayers::<T>(r)?;
+        Module::<T>::set_relayer_threshold(r)?;
+        let proposal = make_proposal::<T>();
+        let caller = relayers.pop().expect("at least one relayer");
+        for relayer in relayers {
+            Module::<T>::vote_for(relayer, NONCE, src_id, Box::new(proposal.clone()))?;
+        }
+    }: _(RawOrigin::Signed(caller), NONCE, src_id, r_id, Box::new(proposal.clone()))
+    verify {
+        let prop_hash = Module::<T>::proposal_hash(&proposal);
+        let votes = Module::<T>::votes(src_id, (NONCE, prop_hash)).unwrap();
+        assert_eq!(votes.status, ProposalStatus::Rejected);
+    }
+
+    // All but the last relayer have voted in favour of each proposal, the votes of the batch
+    // reach the threshold and execute them.
+    acknowledge_proposals_batch {
+        let r in 1 .. T::MaxRelayers::get();
+        let b in 1 .. T::MaxBatchSize::get();
+
+        let (src_id, r_id) = setup_bridge::<T>()?;
+        let mut relayers = setup_relayers::<T>(r)?;
+        Module::<T>::set_relayer_threshold(r)?;
+        let caller = relayers.pop().expect("at least one relayer");
+        let mut proposals = vec![];
+        for nonce in 0..b as DepositNonce {
+            let proposal = make_proposal::<T>();
+            for relayer in &relayers {
+                Module::<T>::vote_for(relayer.clone(), nonce, src_id, Box::new(proposal.clone()))?;
+            }
+            proposals.push((nonce, src_id, r_id, proposal));
+        }
+    }: _(RawOrigin::Signed(caller), proposals)
+    verify {
+        let prop_hash = Module::<T>::proposal_hash(&make_proposal::<T>());
+        for nonce in 0..b as DepositNonce {
+            let votes = Module::<T>::votes(src_id, (nonce, prop_hash)).unwrap();
+            assert_eq!(votes.status, ProposalStatus::Executed);
+        }
+    }
+
+    // All but the last relayer have voted in favour of each proposal, the votes of the batch
+    // reject them.
+    reject_proposals_batch {
+        let r in 1 .. T::MaxRelayers::get();
+        let b in 1 .. T::MaxBatchSize::get();
+
+        let (src_id, r_id) = setup_bridge::<T>()?;
+        let mut relayers = setup_relayers::<T>(r)?;
+        Module::<T>::set_relayer_threshold(r)?;
+        let caller = relayers.pop().expect("at least one relayer");
+        let mut proposals = vec![];
+        for nonce in 0..b as DepositNonce {
+            let proposal = make_proposal::<T>();
+            for relayer in &relayers {
+                Module::<T>::vote_for(relayer.clone(), nonce, src_id, Box::new(proposal.clone()))?;
+            }
+            proposals.push((nonce, src_id, r_id, proposal));
+        }
+    }: _(RawOrigin::Signed(caller), proposals)
+    verify {
+        let prop_hash = Module::<T>::proposal_hash(&make_proposal::<T>());
+        for nonce in 0..b as DepositNonce {
+            let votes = Module::<T>::votes(src_id, (nonce, prop_hash)).unwrap();
+            assert_eq!(votes.status, ProposalStatus::Rejected);
+        }
+    }
+
+    // All but the last relayer have voted in favour, the last vote reaches the threshold
+    // and executes the deposit.
+    acknowledge_deposit {
+        let r in 1 .. T::MaxRelayers::get();
+
+        let (src_id, r_id) = setup_bridge::<T>()?;
+        let mut relayers = setup_relayers::<T>(r)?;
+        Module::<T>::set_relayer_threshold(r)?;
+        let data = make_deposit();
+        let caller = relayers.pop().expect("at least one relayer");
+        for relayer in relayers {
+            let content = ProposalContent::Deposit(r_id, data.clone());
+            Module::<T>::vote(relayer, NONCE, src_id, content, true)?;
+        }
+    }: _(RawOrigin::Signed(caller), NONCE, src_id, r_id, data.clone())
+    verify {
+        let prop_hash = Module::<T>::deposit_hash(r_id, &data);
+        let votes = Module::<T>::votes(src_id, (NONCE, prop_hash)).unwrap();
+        assert_ne!(votes.status, ProposalStatus::Initiated);
+    }
+
+    // All but the last relayer have voted in favour, the last vote against rejects the deposit.
+    reject_deposit {
+        let r in 1 .. T::MaxRelayers::get();
+
+        let (src_id, r_id) = setup_bridge::<T>()?;
+        let mut relayers = setup_relayers::<T>(r)?;
+        Module::<T>::set_relayer_threshold(r)?;
+        let data = make_deposit();
+        let caller = relayers.pop().expect("at least one relayer");
+        for relayer in relayers {
+            let content = ProposalContent::Deposit(r_id, data.clone());
+            Module::<T>::vote(relayer, NONCE, src_id, content, true)?;
+        }
+    }: _(RawOrigin::Signed(caller), NONCE, src_id, r_id, data.clone())
+    verify {
+        let prop_hash = Module::<T>::deposit_hash(r_id, &data);
+        let votes = Module::<T>::votes(src_id, (NONCE, prop_hash)).unwrap();
+        assert_eq!(votes.status, ProposalStatus::Rejected);
+    }
+
+    // Every relayer signs the proposal, the signatures reach the threshold and execute it.
+    submit_signed_proposal {
+        let s in 1 .. T::MaxRelayers::get();
+
+        let (src_id, r_id) = setup_bridge::<T>()?;
+        let relayers = setup_relayers::<T>(s)?;
+        Module::<T>::set_relayer_threshold(s)?;
+        let proposal = make_proposal::<T>();
+        let prop_hash = Module::<T>::proposal_hash(&proposal);
+        let payload = Module::<T>::signed_proposal_payload(src_id, NONCE, r_id, prop_hash);
+        let mut signatures = vec![];
+        for relayer in relayers {
+            let key = sp_io::crypto::sr25519_generate(KEY_TYPE, None);
+            let signature = sp_io::crypto::sr25519_sign(KEY_TYPE, &key, &payload)
+                .ok_or("couldn't make signature")?;
+            RelayerKeys::<T>::insert(&relayer, MultiSigner::Sr25519(key));
+            signatures.push((relayer, MultiSignature::Sr25519(signature)));
+        }
+        let caller: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Signed(caller), NONCE, src_id, r_id, Box::new(proposal), signatures)
+    verify {
+        let votes = Module::<T>::votes(src_id, (NONCE, prop_hash)).unwrap();
+        assert_eq!(votes.status, ProposalStatus::Executed);
+    }
+
+    // All but one relayer have voted in favour, but the threshold was lowered afterwards.
+    eval_vote_state {
+        let r in 1 .. T::MaxRelayers::get() - 1;
+
+        let (src_id, _) = setup_bridge::<T>()?;
+        let mut relayers = setup_relayers::<T>(r + 1)?;
+        Module::<T>::set_relayer_threshold(r + 1)?;
+        let proposal = make_proposal::<T>();
+        relayers.pop();
+        for relayer in relayers {
+            Module::<T>::vote_for(relayer, NONCE, src_id, Box::new(proposal.clone()))?;
+        }
+        Module::<T>::set_relayer_threshold(r)?;
+        let caller: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Signed(caller), NONCE, src_id, Box::new(proposal.clone()))
+    verify {
+        let prop_hash = Module::<T>::proposal_hash(&proposal);
+        let votes = Module::<T>::votes(src_id, (NONCE, prop_hash)).unwrap();
+        assert_eq!(votes.status, ProposalStatus::Executed);
+    }
+
+    // All but one relayer have voted in favour of the deposit, but the threshold was lowered
+    // afterwards.
+    eval_deposit_state {
+        let r in 1 .. T::MaxRelayers::get() - 1;
+
+        let (src_id, r_id) = setup_bridge::<T>()?;
+        let mut relayers = setup_relayers::<T>(r + 1)?;
+        Module::<T>::set_relayer_threshold(r + 1)?;
+        let data = make_deposit();
+        relayers.pop();
+        for relayer in relayers {
+            let content = ProposalContent::Deposit(r_id, data.clone());
+            Module::<T>::vote(relayer, NONCE, src_id, content, true)?;
+        }
+        Module::<T>::set_relayer_threshold(r)?;
+        let caller: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Signed(caller), NONCE, src_id, r_id, data.clone())
+    verify {
+        let prop_hash = Module::<T>::deposit_hash(r_id, &data);
+        let votes = Module::<T>::votes(src_id, (NONCE, prop_hash)).unwrap();
+        assert_ne!(votes.status, ProposalStatus::Initiated);
+    }
+
+    clean_proposal {
+        let (src_id, _) = setup_bridge::<T>()?;
+        let relayer = setup_relayers::<T>(2)?.remove(0);
+        Module::<T>::set_relayer_threshold(2)?;
+        let proposal = make_proposal::<T>();
+        Module::<T>::vote_for(relayer, NONCE, src_id, Box::new(proposal.clone()))?;
+
+        let prop_hash = Module::<T>::proposal_hash(&proposal);
+        let expiry = Module::<T>::votes(src_id, (NONCE, prop_hash)).unwrap().expiry;
+        frame_system::Pallet::<T>::set_block_number(expiry.saturating_add(T::ProposalRetention::get()));
+        let caller: T::AccountId = whitelisted_caller();
+    }: _(RawOrigin::Signed(caller), src_id, NONCE, prop_hash)
+    verify {
+        assert!(Module::<T>::votes(src_id, (NONCE, prop_hash)).is_none());
+    }
+
+    // The call requires root, so the proposal keeps failing.
+    retry_proposal {
+        let (src_id, _) = setup_bridge::<T>()?;
+        let relayer = setup_relayers::<T>(1)?.remove(0);
+        let proposal: T::Proposal = frame_system::Call::<T>::set_heap_pages(0).into();
+        Module::<T>::vote_for(relayer.clone(), NONCE, src_id, Box::new(proposal.clone()))?;
+    }: _(RawOrigin::Signed(relayer), NONCE, src_id, Box::new(proposal))
+    verify {
+        assert_eq!(Module::<T>::proposal_retries(src_id, NONCE), 1);
+    }
+
+    // The deposit is marked as failed, the handler of the runtime decides whether the retry
+    // succeeds.
+    retry_deposit {
+        let (src_id, r_id) = setup_bridge::<T>()?;
+        let relayer = setup_relayers::<T>(2)?.remove(0);
+        Module::<T>::set_relayer_threshold(2)?;
+        let data = make_deposit();
+        let content = ProposalContent::Deposit(r_id, data.clone());
+        Module::<T>::vote(relayer.clone(), NONCE, src_id, content, true)?;
+        let prop_hash = Module::<T>::deposit_hash(r_id, &data);
+        Proposals::<T>::mutate(src_id, (NONCE, prop_hash), |votes| {
+            if let Some(votes) = votes {
+                votes.status = ProposalStatus::Failed;
+            }
+        });
+    }: _(RawOrigin::Signed(relayer), NONCE, src_id, r_id, data)
+    verify {
+        assert_eq!(Module::<T>::proposal_retries(src_id, NONCE), 1);
+    }
+
+    // Every scheduled proposal is still pending and gets removed.
+    prune_proposals {
+        let p in 0 .. MAX_PRUNED;
+
+        let (src_id, _) = setup_bridge::<T>()?;
+        let relayer = setup_relayers::<T>(2)?.remove(0);
+        Module::<T>::set_relayer_threshold(2)?;
+        let prune_at = frame_system::Pallet::<T>::block_number()
+            + T::ProposalLifetime::get()
+            + T::ProposalRetention::get();
+        for nonce in 0..p {
+            let proposal = make_proposal::<T>();
+            Module::<T>::vote_for(relayer.clone(), nonce.into(), src_id, Box::new(proposal))?;
+        }
+    }: {
+        Module::<T>::prune_scheduled_proposals(prune_at);
+    }
+    verify {
+        assert!(Module::<T>::prune_queue(prune_at).is_empty());
+    }
+
+    // Every scheduled record gets removed.
+    prune_deposit_records {
+        let d in 0 .. MAX_PRUNED;
+
+        let (dest_id, r_id) = setup_bridge::<T>()?;
+        let depositor: T::AccountId = whitelisted_caller();
+        let prune_at = frame_system::Pallet::<T>::block_number()
+            + T::DepositRecordRetention::get();
+        for _ in 0..d {
+            Module::<T>::transfer_generic(depositor.clone(), dest_id, r_id, vec![0; 32])?;
+        }
+    }: {
+        Module::<T>::prune_deposit_records(prune_at);
+    }
+    verify {
+        assert!(Module::<T>::deposit_prune_queue(prune_at).is_empty());
+    }
+}
+
+impl_benchmark_test_suite!(Module, crate::mock::new_test_ext(), crate::mock::Test);
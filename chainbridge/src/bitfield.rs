@@ -0,0 +1,72 @@
+//! Compact sets of relayer slots
+
+use super::*;
+use sp_std::iter::FromIterator;
+
+/// Set of relayer slots, stored as one bit per slot.
+///
+/// Trailing empty bytes are never stored, so equal sets have the same encoding.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Bitfield(Vec<u8>);
+
+impl Bitfield {
+    /// Returns true if the slot is set
+    pub fn contains(&self, slot: u32) -> bool {
+        let (byte, mask) = Self::position(slot);
+        self.0.get(byte).map_or(false, |b| b & mask != 0)
+    }
+
+    /// Sets a slot, extending the bitfield as needed
+    pub fn insert(&mut self, slot: u32) {
+        let (byte, mask) = Self::position(slot);
+        if self.0.len() <= byte {
+            self.0.resize(byte + 1, 0);
+        }
+        self.0[byte] |= mask;
+    }
+
+    /// Clears a slot
+    pub fn remove(&mut self, slot: u32) {
+        let (byte, mask) = Self::position(slot);
+        if let Some(b) = self.0.get_mut(byte) {
+            *b &= !mask;
+        }
+        while self.0.last() == Some(&0) {
+            self.0.pop();
+        }
+    }
+
+    /// Returns the number of slots set
+    pub fn count(&self) -> u32 {
+        self.0.iter().map(|b| b.count_ones()).sum()
+    }
+
+    /// Returns the lowest slot that isn't set
+    pub fn first_free(&self) -> u32 {
+        match self.0.iter().position(|b| *b != u8::max_value()) {
+            Some(byte) => byte as u32 * 8 + self.0[byte].trailing_ones(),
+            None => self.0.len() as u32 * 8,
+        }
+    }
+
+    /// Iterates over the slots that are set, in ascending order
+    pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        (0..self.0.len() as u32 * 8).filter(move |slot| self.contains(*slot))
+    }
+
+    /// Byte index and bit mask of a slot
+    fn position(slot: u32) -> (usize, u8) {
+        ((slot / 8) as usize, 1 << (slot % 8))
+    }
+}
+
+impl FromIterator<u32> for Bitfield {
+    fn from_iter<I: IntoIterator<Item = u32>>(slots: I) -> Self {
+        let mut bitfield = Bitfield::default();
+        for slot in slots {
+            bitfield.insert(slot);
+        }
+        bitfield
+    }
+}
@@ -0,0 +1,129 @@
+//! Handlers for deposits bridged from other chains
+
+use super::*;
+
+/// Payload of a deposit, decoded according to the kind of its resource
+#[derive(PartialEq, Eq, Clone, RuntimeDebug)]
+pub enum Deposit {
+    Fungible {
+        amount: U256,
+        recipient: Vec<u8>,
+    },
+    NonFungible {
+        token_id: U256,
+        recipient: Vec<u8>,
+        metadata: Vec<u8>,
+    },
+    Generic {
+        metadata: Vec<u8>,
+    },
+}
+
+impl Deposit {
+    /// Decodes deposit data in the format used by the ChainBridge Solidity handlers:
+    ///
+    /// - fungible: `amount (32) | recipient length (32) | recipient`
+    /// - non-fungible: `token ID (32) | recipient length (32) | recipient | metadata length (32) | metadata`
+    /// - generic: `metadata length (32) | metadata`
+    ///
+    /// Returns `None` if the data is malformed or has trailing bytes.
+    pub fn parse(kind: ResourceKind, data: &[u8]) -> Option<Self> {
+        let mut input = data;
+        let deposit = match kind {
+            ResourceKind::Fungible => Deposit::Fungible {
+                amount: read_word(&mut input)?,
+                recipient: read_bytes(&mut input)?,
+            },
+            ResourceKind::NonFungible => Deposit::NonFungible {
+                token_id: read_word(&mut input)?,
+                recipient: read_bytes(&mut input)?,
+                metadata: read_bytes(&mut input)?,
+            },
+            ResourceKind::Generic => Deposit::Generic {
+                metadata: read_bytes(&mut input)?,
+            },
+        };
+        if input.is_empty() {
+            Some(deposit)
+        } else {
+            None
+        }
+    }
+
+    /// Encodes the deposit in the format expected by `parse`
+    pub fn to_data(&self) -> Vec<u8> {
+        let mut data = vec![];
+        match self {
+            Deposit::Fungible { amount, recipient } => {
+                write_word(&mut data, *amount);
+                write_bytes(&mut data, recipient);
+            }
+            Deposit::NonFungible {
+                token_id,
+                recipient,
+                metadata,
+            } => {
+                write_word(&mut data, *token_id);
+                write_bytes(&mut data, recipient);
+                write_bytes(&mut data, metadata);
+            }
+            Deposit::Generic { metadata } => write_bytes(&mut data, metadata),
+        }
+        data
+    }
+}
+
+/// Reads a 32 byte big endian word
+fn read_word(input: &mut &[u8]) -> Option<U256> {
+    if input.len() < 32 {
+        return None;
+    }
+    let (word, rest) = input.split_at(32);
+    *input = rest;
+    Some(U256::from_big_endian(word))
+}
+
+/// Reads bytes prefixed by their length as a 32 byte word
+fn read_bytes(input: &mut &[u8]) -> Option<Vec<u8>> {
+    let len = read_word(input)?;
+    if len > U256::from(input.len()) {
+        return None;
+    }
+    let (bytes, rest) = input.split_at(len.as_usize());
+    *input = rest;
+    Some(bytes.to_vec())
+}
+
+fn write_word(output: &mut Vec<u8>, word: U256) {
+    let mut bytes = [0u8; 32];
+    word.to_big_endian(&mut bytes);
+    output.extend_from_slice(&bytes);
+}
+
+fn write_bytes(output: &mut Vec<u8>, bytes: &[u8]) {
+    write_word(output, U256::from(bytes.len()));
+    output.extend_from_slice(bytes);
+}
+
+/// Executes deposits bridged from other chains.
+///
+/// The runtime provides a single handler, which routes deposits by their resource ID or the
+/// `handler` of the resource.
+pub trait ResourceHandler {
+    /// Maximum weight of handling a deposit with the given data
+    fn weight(r_id: ResourceId, data: &[u8]) -> Weight;
+
+    /// Executes a deposit for a resource
+    fn handle(r_id: ResourceId, info: &ResourceInfo, deposit: Deposit) -> DispatchResult;
+}
+
+/// Rejects all deposits
+impl ResourceHandler for () {
+    fn weight(_: ResourceId, _: &[u8]) -> Weight {
+        0
+    }
+
+    fn handle(_: ResourceId, _: &ResourceInfo, _: Deposit) -> DispatchResult {
+        Err(DispatchError::Other("No resource handler"))
+    }
+}
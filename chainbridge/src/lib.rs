@@ -3,28 +3,53 @@
 
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
-    dispatch::DispatchResult,
+    dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo, PostDispatchInfo},
     ensure,
-    traits::{EnsureOrigin, Get},
-    weights::{GetDispatchInfo, Pays},
+    storage::{with_transaction, IterableStorageMap},
+    traits::{
+        CallMetadata, Currency, EnsureOrigin, ExistenceRequirement, Get, GetCallMetadata,
+        OnUnbalanced, ReservableCurrency,
+    },
+    weights::{extract_actual_weight, GetDispatchInfo, Pays, Weight},
     Parameter, PalletId,
 };
 
 use frame_system::{self as system, ensure_root, ensure_signed};
 use sp_core::U256;
-use sp_runtime::traits::{AccountIdConversion, Dispatchable};
-use sp_runtime::RuntimeDebug;
+use sp_runtime::traits::{AccountIdConversion, Dispatchable, Hash, Saturating, Verify, Zero};
+use sp_runtime::{MultiSignature, MultiSigner, Perbill, RuntimeDebug, TransactionOutcome};
 use sp_std::prelude::*;
 
 use codec::{Decode, Encode, EncodeLike};
-
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod bitfield;
+pub mod handler;
+pub mod migrations;
 mod mock;
 mod tests;
+pub mod weights;
+
+pub use bitfield::Bitfield;
+pub use handler::{Deposit, ResourceHandler};
+pub use weights::WeightInfo;
 
 const DEFAULT_RELAYER_THRESHOLD: u32 = 1;
 const MODULE_ID: PalletId = PalletId(*b"cb/bridg");
+const REWARDS_SUB_ACCOUNT: &[u8; 7] = b"rewards";
+const FEES_SUB_ACCOUNT: &[u8; 4] = b"fees";
 
 pub type ChainId = u8;
+pub type BalanceOf<T> =
+    <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+    <T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+pub type FeeBalanceOf<T> =
+    <<T as Config>::FeeCurrency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 pub type DepositNonce = u64;
 pub type ResourceId = [u8; 32];
 
@@ -40,29 +65,192 @@ pub fn derive_resource_id(chain: u8, id: &[u8]) -> ResourceId {
     return r_id;
 }
 
+/// Verifies a signature of `message`. The signature and key must be of the same scheme.
+/// ECDSA signatures are made over the blake2-256 hash of the message.
+pub fn verify_signature(signature: &MultiSignature, key: &MultiSigner, message: &[u8]) -> bool {
+    match (signature, key) {
+        (MultiSignature::Ed25519(sig), MultiSigner::Ed25519(key)) => sig.verify(message, key),
+        (MultiSignature::Sr25519(sig), MultiSigner::Sr25519(key)) => sig.verify(message, key),
+        (MultiSignature::Ecdsa(sig), MultiSigner::Ecdsa(key)) => sig.verify(message, key),
+        _ => false,
+    }
+}
+
+/// Storage layout versions of the pallet, used to guard migrations.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum Releases {
+    /// Proposal votes keyed by the full encoded call
+    V1,
+    /// Proposal votes keyed by the call hash, calls stored separately
+    V2,
+    /// Resources described by `ResourceInfo` instead of a method name
+    V3,
+    /// Relayers, chains and resources stored in enumerable maps
+    V4,
+    /// Votes stored as bitfields of relayer slots
+    V5,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1
+    }
+}
+
+/// Transfer directions permitted for a whitelisted chain
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum ChainDirection {
+    /// Proposals from and transfers to the chain are permitted
+    Both,
+    /// Only proposals from the chain are permitted
+    Inbound,
+    /// Only transfers to the chain are permitted
+    Outbound,
+}
+
+impl ChainDirection {
+    /// Returns true if proposals from the chain are permitted
+    pub fn allows_inbound(&self) -> bool {
+        *self != ChainDirection::Outbound
+    }
+
+    /// Returns true if transfers to the chain are permitted
+    pub fn allows_outbound(&self) -> bool {
+        *self != ChainDirection::Inbound
+    }
+}
+
+impl Default for ChainDirection {
+    fn default() -> Self {
+        ChainDirection::Both
+    }
+}
+
+/// Kind of asset or data transferred with a resource
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum ResourceKind {
+    Fungible,
+    NonFungible,
+    Generic,
+}
+
+/// Describes a registered resource
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ResourceInfo {
+    pub kind: ResourceKind,
+    /// Method handling proposals for the resource (eg. `Example.transfer`)
+    pub handler: Vec<u8>,
+    /// Proposals for the resource are only accepted while enabled
+    pub enabled: bool,
+    /// Decimals of a fungible asset, 0 for other kinds
+    pub decimals: u8,
+    /// Chains the resource may be bridged with, any whitelisted chain if empty
+    pub allowed_chains: Vec<ChainId>,
+}
+
+impl ResourceInfo {
+    /// Creates an enabled resource that may be bridged with any chain
+    pub fn new(kind: ResourceKind, handler: Vec<u8>) -> Self {
+        Self {
+            kind,
+            handler,
+            enabled: true,
+            decimals: 0,
+            allowed_chains: vec![],
+        }
+    }
+
+    /// Returns true if the resource may be bridged with the chain
+    pub fn allows_chain(&self, id: ChainId) -> bool {
+        self.allowed_chains.is_empty() || self.allowed_chains.contains(&id)
+    }
+}
+
+/// Checks whether a proposed call may be executed for a resource
+pub trait ProposalValidator<Proposal> {
+    /// Returns true if `call` is a valid proposal for the resource
+    fn validate(r_id: ResourceId, info: &ResourceInfo, call: &Proposal) -> bool;
+}
+
+/// Accepts any call for any resource
+impl<Proposal> ProposalValidator<Proposal> for () {
+    fn validate(_: ResourceId, _: &ResourceInfo, _: &Proposal) -> bool {
+        true
+    }
+}
+
+/// Accepts calls whose `Pallet.method` name matches the handler of the resource
+pub struct MatchHandler;
+impl<Proposal: GetCallMetadata> ProposalValidator<Proposal> for MatchHandler {
+    fn validate(_: ResourceId, info: &ResourceInfo, call: &Proposal) -> bool {
+        let CallMetadata {
+            pallet_name,
+            function_name,
+        } = call.get_call_metadata();
+        info.handler == [pallet_name.as_bytes(), b".", function_name.as_bytes()].concat()
+    }
+}
+
+/// What relayers vote on, either a call or deposit data for a resource
+enum ProposalContent<Call> {
+    Call(Box<Call>),
+    Deposit(ResourceId, Vec<u8>),
+}
+
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum ProposalStatus {
     Initiated,
     Approved,
     Rejected,
+    /// Approved and the call was executed successfully
+    Executed,
+    /// Approved but the call failed, execution may be retried
+    Failed,
+}
+
+/// Number of votes required for a proposal to be approved
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum VoteThreshold {
+    /// A fixed number of votes
+    Absolute(u32),
+    /// A proportion of the current relayers, rounded up
+    Proportion(Perbill),
+}
+
+impl VoteThreshold {
+    /// Returns the number of votes required with `total` relayers. At least one vote is always
+    /// required.
+    pub fn votes(&self, total: u32) -> u32 {
+        match self {
+            VoteThreshold::Absolute(threshold) => *threshold,
+            VoteThreshold::Proportion(ratio) => ratio.mul_ceil(total).max(1),
+        }
+    }
 }
 
+/// Votes on a proposal, recorded by the slots of the relayers
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
-pub struct ProposalVotes<AccountId, BlockNumber> {
-    pub votes_for: Vec<AccountId>,
-    pub votes_against: Vec<AccountId>,
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ProposalVotes<BlockNumber> {
+    pub votes_for: Bitfield,
+    pub votes_against: Bitfield,
     pub status: ProposalStatus,
     pub expiry: BlockNumber,
 }
 
-impl<A: PartialEq, B: PartialOrd + Default> ProposalVotes<A, B> {
+impl<B: PartialOrd + Default> ProposalVotes<B> {
     /// Attempts to mark the proposal as approve or rejected.
     /// Returns true if the status changes from active.
-    fn try_to_complete(&mut self, threshold: u32, total: u32) -> ProposalStatus {
-        if self.votes_for.len() >= threshold as usize {
+    fn try_to_complete(&mut self, threshold: VoteThreshold, total: u32) -> ProposalStatus {
+        let threshold = threshold.votes(total);
+        if self.votes_for.count() >= threshold {
             self.status = ProposalStatus::Approved;
             ProposalStatus::Approved
-        } else if total >= threshold && self.votes_against.len() as u32 + threshold > total {
+        } else if total >= threshold && self.votes_against.count() + threshold > total {
             self.status = ProposalStatus::Rejected;
             ProposalStatus::Rejected
         } else {
@@ -75,9 +263,9 @@ impl<A: PartialEq, B: PartialOrd + Default> ProposalVotes<A, B> {
         self.status != ProposalStatus::Initiated
     }
 
-    /// Returns true if `who` has voted for or against the proposal
-    fn has_voted(&self, who: &A) -> bool {
-        self.votes_for.contains(&who) || self.votes_against.contains(&who)
+    /// Returns true if the relayer in `slot` has voted for or against the proposal
+    fn has_voted(&self, slot: u32) -> bool {
+        self.votes_for.contains(slot) || self.votes_against.contains(slot)
     }
 
     /// Return true if the expiry time has been reached
@@ -86,46 +274,160 @@ impl<A: PartialEq, B: PartialOrd + Default> ProposalVotes<A, B> {
     }
 }
 
-impl<AccountId, BlockNumber: Default> Default for ProposalVotes<AccountId, BlockNumber> {
+impl<BlockNumber: Default> Default for ProposalVotes<BlockNumber> {
     fn default() -> Self {
         Self {
-            votes_for: vec![],
-            votes_against: vec![],
+            votes_for: Bitfield::default(),
+            votes_against: Bitfield::default(),
             status: ProposalStatus::Initiated,
             expiry: BlockNumber::default(),
         }
     }
 }
 
+/// Record of a transfer out of the chain, kept so relayers can recover missed deposits
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DepositRecord<AccountId, BlockNumber> {
+    pub resource_id: ResourceId,
+    /// Deposit data in the format of `Deposit::to_data`, its hash is emitted with the transfer event
+    pub payload: Vec<u8>,
+    pub depositor: AccountId,
+    /// Block in which the transfer was made
+    pub block: BlockNumber,
+}
+
+/// Funds reserved by a relayer while it is registered and until its bond is withdrawn
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct Bond<Balance, BlockNumber> {
+    pub amount: Balance,
+    /// Slot of the votes of the relayer
+    pub slot: u32,
+    /// Block in which the relayer was assigned the slot
+    pub since: BlockNumber,
+    /// Block in which the relayer was removed, the bond unbonds from then on
+    pub until: Option<BlockNumber>,
+}
+
 pub trait Config: system::Config {
     type Event: From<Event<Self>> + Into<<Self as frame_system::Config>::Event>;
     /// Origin used to administer the pallet
     type AdminOrigin: EnsureOrigin<Self::Origin>;
+    /// Origin allowed to pause the bridge, in addition to the admin origin
+    type PauseOrigin: EnsureOrigin<Self::Origin>;
     /// Proposed dispatchable call
     type Proposal: Parameter + Dispatchable<Origin = Self::Origin> + EncodeLike + GetDispatchInfo;
     /// The identifier for this chain.
     /// This must be unique and must not collide with existing IDs within a set of bridged chains.
     type ChainId: Get<ChainId>;
+    /// Checks that proposed calls match the resource they are submitted for
+    type ProposalValidator: ProposalValidator<Self::Proposal>;
+    /// Executes deposits for resources, see `acknowledge_deposit`
+    type ResourceHandler: ResourceHandler;
+
+    /// Maximum number of relayers, which also bounds the size of the votes of each proposal.
+    /// Must not be lowered below the current number of relayers.
+    type MaxRelayers: Get<u32>;
 
     type ProposalLifetime: Get<Self::BlockNumber>;
+
+    /// Number of blocks a proposal is kept after its expiry, before it is removed from storage.
+    type ProposalRetention: Get<Self::BlockNumber>;
+
+    /// Number of times the execution of a failed proposal may be retried.
+    type MaxProposalRetries: Get<u32>;
+
+    /// Number of blocks deposit records are kept for. Records are never removed if zero.
+    type DepositRecordRetention: Get<Self::BlockNumber>;
+
+    /// Maximum number of proposals a relayer may vote on in a single batch.
+    type MaxBatchSize: Get<u32>;
+
+    /// Currency in which relayers are bonded
+    type Currency: ReservableCurrency<Self::AccountId>;
+
+    /// Amount reserved from a relayer when it is added to the set
+    type RelayerBond: Get<BalanceOf<Self>>;
+
+    /// Number of blocks after the removal of a relayer until its bond can be withdrawn.
+    /// The bond is always kept for at least `ProposalLifetime + ProposalRetention` blocks, so
+    /// misbehaviour can be reported until the votes of the relayer are removed.
+    type UnbondingDelay: Get<Self::BlockNumber>;
+
+    /// Handler for the bonds slashed from misbehaving relayers
+    type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+    /// Amount issued into the reward pot for each executed proposal. The pot is only funded by
+    /// transfers into it if zero.
+    type ProposalReward: Get<BalanceOf<Self>>;
+
+    /// Currency in which outbound transfers are charged fees. Fees are collected into the fee
+    /// account, from which they are withdrawn by `withdraw_fees`.
+    type FeeCurrency: Currency<Self::AccountId>;
+
+    /// Relayers registered before storage version V4. Their accounts can't be recovered from
+    /// the opaque keys of the old `Relayers` map, so `on_runtime_upgrade` only keeps those
+    /// listed here. Can be empty once the storage is migrated.
+    type LegacyRelayers: Get<Vec<Self::AccountId>>;
+
+    /// Resource IDs registered before storage version V4, see `LegacyRelayers`
+    type LegacyResourceIds: Get<Vec<ResourceId>>;
+
+    /// Weight information for extrinsics in this pallet.
+    type WeightInfo: WeightInfo;
 }
 
 decl_event! {
-    pub enum Event<T> where <T as frame_system::Config>::AccountId {
+    pub enum Event<T> where
+        <T as frame_system::Config>::AccountId,
+        Hash = <T as frame_system::Config>::Hash,
+        Balance = BalanceOf<T>,
+        Fee = FeeBalanceOf<T>,
+    {
         /// Vote threshold has changed (new_threshold)
         RelayerThresholdChanged(u32),
+        /// Proportional vote threshold has been set or cleared (new_ratio)
+        RelayerThresholdRatioChanged(Option<Perbill>),
         /// Chain now available for transfers (chain_id)
         ChainWhitelisted(ChainId),
+        /// Chain no longer available for transfers (chain_id)
+        ChainRemoved(ChainId),
+        /// Permitted transfer directions for a chain have changed (chain_id, direction)
+        ChainDirectionChanged(ChainId, ChainDirection),
+        /// Resource registered or updated (resource_id, kind)
+        ResourceRegistered(ResourceId, ResourceKind),
+        /// Resource removed (resource_id)
+        ResourceRemoved(ResourceId),
+        /// Fee for transfers of a resource to a chain has changed (dest_id, resource_id, fee)
+        FeeChanged(ChainId, ResourceId, Fee),
         /// Relayer added to set
         RelayerAdded(AccountId),
         /// Relayer removed from set
         RelayerRemoved(AccountId),
-        /// FunglibleTransfer is for relaying fungibles (dest_id, nonce, resource_id, amount, recipient, metadata)
-        FungibleTransfer(ChainId, DepositNonce, ResourceId, U256, Vec<u8>),
-        /// NonFungibleTransfer is for relaying NFTS (dest_id, nonce, resource_id, token_id, recipient, metadata)
-        NonFungibleTransfer(ChainId, DepositNonce, ResourceId, Vec<u8>, Vec<u8>, Vec<u8>),
-        /// GenericTransfer is for a generic data payload (dest_id, nonce, resource_id, metadata)
-        GenericTransfer(ChainId, DepositNonce, ResourceId, Vec<u8>),
+        /// Relayer has set the key it signs proposals with
+        RelayerKeySet(AccountId),
+        /// Bond of a relayer was slashed for voting for a conflicting proposal (relayer, amount)
+        RelayerSlashed(AccountId, Balance),
+        /// Bond of a removed relayer was released (relayer, amount)
+        BondWithdrawn(AccountId, Balance),
+        /// Relayer was paid its share of the reward pot (relayer, amount)
+        RewardsClaimed(AccountId, Balance),
+        /// Collected fees were withdrawn from the fee account (recipient, amount)
+        FeesWithdrawn(AccountId, Fee),
+        /// All bridge transfers and proposals are halted
+        BridgePaused,
+        /// Bridge transfers and proposals are resumed
+        BridgeUnpaused,
+        /// Transfers and proposals for a chain are halted (chain_id)
+        ChainPaused(ChainId),
+        /// Transfers and proposals for a chain are resumed (chain_id)
+        ChainUnpaused(ChainId),
+        /// FunglibleTransfer is for relaying fungibles (dest_id, nonce, resource_id, amount, recipient, depositor, payload_hash, fee)
+        FungibleTransfer(ChainId, DepositNonce, ResourceId, U256, Vec<u8>, AccountId, Hash, Fee),
+        /// NonFungibleTransfer is for relaying NFTS (dest_id, nonce, resource_id, token_id, recipient, metadata, depositor, payload_hash, fee)
+        NonFungibleTransfer(ChainId, DepositNonce, ResourceId, Vec<u8>, Vec<u8>, Vec<u8>, AccountId, Hash, Fee),
+        /// GenericTransfer is for a generic data payload (dest_id, nonce, resource_id, metadata, depositor, payload_hash, fee)
+        GenericTransfer(ChainId, DepositNonce, ResourceId, Vec<u8>, AccountId, Hash, Fee),
         /// Vote submitted in favour of proposal
         VoteFor(ChainId, DepositNonce, AccountId),
         /// Vot submitted against proposal
@@ -136,8 +438,19 @@ decl_event! {
         ProposalRejected(ChainId, DepositNonce),
         /// Execution of call succeeded
         ProposalSucceeded(ChainId, DepositNonce),
-        /// Execution of call failed
-        ProposalFailed(ChainId, DepositNonce),
+        /// Execution of call failed (src_id, nonce, error)
+        ProposalFailed(ChainId, DepositNonce, DispatchError),
+        /// Proposal was removed without being completed before its expiry
+        ProposalExpired(ChainId, DepositNonce),
+        /// Vote of a batch committed (src_id, nonce)
+        BatchVoteSucceeded(ChainId, DepositNonce),
+        /// Vote of a batch failed, other votes of the batch are unaffected (src_id, nonce, error)
+        BatchVoteFailed(ChainId, DepositNonce, DispatchError),
+        /// Pending proposals made before V2 were dropped by the storage migration, relayers must
+        /// vote on them again (count)
+        LegacyProposalsDropped(u32),
+        /// Completed proposals made before V2 were removed (count)
+        LegacyVotesPurged(u32),
     }
 }
 
@@ -149,14 +462,28 @@ decl_error! {
         InvalidChainId,
         /// Relayer threshold cannot be 0
         InvalidThreshold,
+        /// Relayer threshold would exceed the number of relayers
+        ThresholdExceedsRelayers,
         /// Interactions with this chain is not permitted
         ChainNotWhitelisted,
         /// Chain has already been enabled
         ChainAlreadyWhitelisted,
         /// Resource ID provided isn't mapped to anything
         ResourceDoesNotExist,
+        /// Resource is registered but currently disabled
+        ResourceDisabled,
+        /// Resource may not be bridged with this chain
+        ResourceChainNotAllowed,
+        /// Proposed call does not match the handler of the resource
+        InvalidProposal,
+        /// Deposit data can't be decoded for the kind of the resource
+        InvalidDepositData,
         /// Relayer already in set
         RelayerAlreadyExists,
+        /// Relayer set has reached `MaxRelayers`
+        TooManyRelayers,
+        /// All free relayer slots still have votes of removed relayers
+        SlotCoolingDown,
         /// Provided accountId is not a relayer
         RelayerInvalid,
         /// Protected operation, must be performed by relayer
@@ -173,32 +500,204 @@ decl_error! {
         ProposalAlreadyComplete,
         /// Lifetime of proposal has been exceeded
         ProposalExpired,
+        /// Proposal was created before its source chain was removed
+        ProposalFromRemovedChain,
+        /// Proposal must be kept until its retention period has passed
+        ProposalNotPrunable,
+        /// A proposal for this deposit nonce and source chain has already been executed
+        NonceAlreadyExecuted,
+        /// Only proposals whose execution failed can be retried
+        ProposalNotFailed,
+        /// Execution of the proposal has been retried too many times
+        RetryLimitReached,
+        /// The bridge or the chain involved is paused
+        BridgePaused,
+        /// Transfers in this direction are not permitted for the chain
+        DirectionNotAllowed,
+        /// Token ID of a non-fungible transfer exceeds 32 bytes
+        InvalidTokenId,
+        /// Batch contains more than `MaxBatchSize` proposals
+        BatchTooLarge,
+        /// Relayer has not set a key to sign proposals with
+        RelayerKeyNotSet,
+        /// Signature does not match the proposal or the key of the relayer
+        InvalidSignature,
+        /// Proposal is signed more than once by the same relayer
+        DuplicateSignature,
+        /// Signatures do not reach the vote threshold
+        InsufficientSignatures,
+        /// Account has no bond
+        NotBonded,
+        /// Bond can't be withdrawn while the account is a relayer
+        RelayerStillRegistered,
+        /// Unbonding delay has not passed yet
+        UnbondingNotFinished,
+        /// Relayer did not vote for a proposal conflicting with an executed one while it held
+        /// its slot
+        InvalidReport,
+        /// Relayer has no reward points to claim
+        NoRewards,
+        /// Fee is below the existential deposit of the fee currency
+        FeeBelowMinimum,
     }
 }
 
 decl_storage! {
     trait Store for Module<T: Config> as ChainBridge {
-        /// All whitelisted chains and their respective transaction counts
-        ChainNonces get(fn chains): map hasher(opaque_blake2_256) ChainId => Option<DepositNonce>;
+        /// All whitelisted chains and their respective transaction counts.
+        /// Removed chains keep their nonce, in case they are whitelisted again.
+        ChainNonces get(fn chains): map hasher(blake2_128_concat) ChainId => Option<DepositNonce>;
+
+        /// Chains that have been removed after being whitelisted
+        pub RemovedChains get(fn chain_removed): map hasher(opaque_blake2_256) ChainId => bool;
+
+        /// Block in which each chain was last removed. Proposals from the chain created until
+        /// then are refused, even once the chain is whitelisted again.
+        pub ChainRemovals get(fn chain_removed_at):
+            map hasher(opaque_blake2_256) ChainId => Option<T::BlockNumber>;
+
+        /// Permitted transfer directions for each whitelisted chain
+        pub ChainDirections get(fn chain_direction):
+            map hasher(opaque_blake2_256) ChainId => ChainDirection;
 
         /// Number of votes required for a proposal to execute
         RelayerThreshold get(fn relayer_threshold): u32 = DEFAULT_RELAYER_THRESHOLD;
 
-        /// Tracks current relayer set
-        pub Relayers get(fn relayers): map hasher(opaque_blake2_256) T::AccountId => bool;
+        /// Proportion of the relayers whose votes are required for a proposal to execute.
+        /// Replaces `RelayerThreshold` while set.
+        RelayerThresholdRatio get(fn relayer_threshold_ratio): Option<Perbill>;
+
+        /// Tracks current relayer set, along with the slot in which the votes of each relayer
+        /// are recorded
+        pub Relayers get(fn relayer_slot):
+            map hasher(blake2_128_concat) T::AccountId => Option<u32>;
+
+        /// Slots assigned to current relayers
+        pub OccupiedSlots get(fn occupied_slots): Bitfield;
+
+        /// Block in which each slot not yet reassigned was freed. A slot is only reassigned once
+        /// the proposals its previous relayer may have voted on have been removed.
+        pub FreedSlots get(fn slot_freed_at):
+            map hasher(twox_64_concat) u32 => Option<T::BlockNumber>;
+
+        /// Keys relayers sign proposals with, see `submit_signed_proposal`
+        pub RelayerKeys get(fn relayer_key):
+            map hasher(blake2_128_concat) T::AccountId => Option<MultiSigner>;
+
+        /// Bonds of current and removed relayers, kept until withdrawn.
+        /// Relayers registered at genesis have no bond.
+        pub Bonds get(fn bond):
+            map hasher(blake2_128_concat) T::AccountId => Option<Bond<BalanceOf<T>, T::BlockNumber>>;
+
+        /// Unclaimed reward points of relayers, earned by voting for executed proposals
+        pub RewardPoints get(fn reward_points):
+            map hasher(blake2_128_concat) T::AccountId => u32;
+
+        /// Sum of all unclaimed reward points
+        pub TotalRewardPoints get(fn total_reward_points): u32;
 
         /// Number of relayers in set
         pub RelayerCount get(fn relayer_count): u32;
 
+        /// Halts all transfers and proposals while set
+        pub BridgePaused get(fn bridge_paused): bool;
+
+        /// Chains for which transfers and proposals are halted
+        pub PausedChains get(fn chain_paused): map hasher(opaque_blake2_256) ChainId => bool;
+
         /// All known proposals.
         /// The key is the hash of the call and the deposit ID, to ensure it's unique.
-        pub Votes get(fn votes):
+        pub Proposals get(fn votes):
+            double_map hasher(opaque_blake2_256) ChainId, hasher(opaque_blake2_256) (DepositNonce, T::Hash)
+            => Option<ProposalVotes<T::BlockNumber>>;
+
+        /// Votes on proposals made before V2, keyed by the full encoded call.
+        /// Deprecated: only completed proposals are kept, so they can't be created again, until
+        /// removed by `purge_legacy_votes`.
+        pub Votes get(fn legacy_votes):
             double_map hasher(opaque_blake2_256) ChainId, hasher(opaque_blake2_256) (DepositNonce, T::Proposal)
-            => Option<ProposalVotes<T::AccountId, T::BlockNumber>>;
+            => Option<migrations::deprecated::ProposalVotes<T::AccountId, T::BlockNumber>>;
+
+        /// Set while `Votes` has entries, new proposals are only checked against it until then
+        pub LegacyVotesRemain get(fn legacy_votes_remain): bool;
+
+        /// Calls of known proposals by their hash, along with the number of proposals referencing them
+        pub ProposalCalls get(fn proposal_call):
+            map hasher(identity) T::Hash => Option<(T::Proposal, u32)>;
+
+        /// Hash of the proposal executed for each deposit nonce of a source chain
+        pub ExecutedNonces get(fn executed_nonce):
+            double_map hasher(opaque_blake2_256) ChainId, hasher(opaque_blake2_256) DepositNonce
+            => Option<T::Hash>;
+
+        /// Number of times the execution of a failed proposal has been retried
+        pub ProposalRetries get(fn proposal_retries):
+            double_map hasher(opaque_blake2_256) ChainId, hasher(opaque_blake2_256) DepositNonce
+            => u32;
+
+        /// Proposals to remove at a given block, once their retention period has passed
+        pub PruneQueue get(fn prune_queue):
+            map hasher(twox_64_concat) T::BlockNumber => Vec<(ChainId, DepositNonce, T::Hash)>;
+
+        /// Transfers made to other chains by destination chain and deposit nonce
+        pub DepositRecords get(fn deposit_record):
+            double_map hasher(blake2_128_concat) ChainId, hasher(twox_64_concat) DepositNonce
+            => Option<DepositRecord<T::AccountId, T::BlockNumber>>;
 
-        /// Utilized by the bridge software to map resource IDs to actual methods
+        /// Deposit records to remove at a given block
+        pub DepositPruneQueue get(fn deposit_prune_queue):
+            map hasher(twox_64_concat) T::BlockNumber => Vec<(ChainId, DepositNonce)>;
+
+        /// Utilized by the bridge software to look up the kind and handler of resource IDs
         pub Resources get(fn resources):
-            map hasher(opaque_blake2_256) ResourceId => Option<Vec<u8>>
+            map hasher(blake2_128_concat) ResourceId => Option<ResourceInfo>;
+
+        /// Fees charged to depositors for transfers of a resource to a chain
+        pub BridgeFees get(fn bridge_fee):
+            map hasher(blake2_128_concat) (ChainId, ResourceId) => FeeBalanceOf<T>;
+
+        /// Storage layout version, used to determine which migrations to run
+        StorageVersion get(fn storage_version) build(|_: &GenesisConfig<T>| Releases::V5): Releases;
+    }
+    add_extra_genesis {
+        /// Initial relayer set
+        config(relayers): Vec<T::AccountId>;
+        /// Vote threshold, must be set if relayers are provided
+        config(threshold): u32;
+        /// Chains whitelisted for transfers
+        config(chains): Vec<ChainId>;
+        /// Registered resources
+        config(resources): Vec<(ResourceId, ResourceInfo)>;
+        build(|config: &GenesisConfig<T>| {
+            if !config.relayers.is_empty() || config.threshold != 0 {
+                assert!(config.threshold > 0, "Relayer threshold cannot be 0");
+                assert!(
+                    config.threshold as usize <= config.relayers.len(),
+                    "Relayer threshold exceeds the number of relayers"
+                );
+                <RelayerThreshold>::put(config.threshold);
+            }
+            assert!(
+                config.relayers.len() <= T::MaxRelayers::get() as usize,
+                "Too many relayers in genesis"
+            );
+            for (slot, relayer) in config.relayers.iter().enumerate() {
+                assert!(
+                    !<Relayers<T>>::contains_key(relayer),
+                    "Duplicate relayer in genesis"
+                );
+                <Relayers<T>>::insert(relayer, slot as u32);
+            }
+            <OccupiedSlots>::put((0..config.relayers.len() as u32).collect::<Bitfield>());
+            <RelayerCount>::put(config.relayers.len() as u32);
+            for id in &config.chains {
+                assert!(*id != T::ChainId::get(), "Cannot whitelist this chain");
+                <ChainNonces>::insert(id, 0);
+            }
+            for (id, info) in &config.resources {
+                <Resources>::insert(id, info);
+            }
+        })
     }
 }
 
@@ -207,34 +706,75 @@ decl_module! {
         type Error = Error<T>;
 
         const ChainIdentity: ChainId = T::ChainId::get();
+        const MaxRelayers: u32 = T::MaxRelayers::get();
         const ProposalLifetime: T::BlockNumber = T::ProposalLifetime::get();
+        const ProposalRetention: T::BlockNumber = T::ProposalRetention::get();
+        const MaxProposalRetries: u32 = T::MaxProposalRetries::get();
+        const DepositRecordRetention: T::BlockNumber = T::DepositRecordRetention::get();
+        const MaxBatchSize: u32 = T::MaxBatchSize::get();
+        const RelayerBond: BalanceOf<T> = T::RelayerBond::get();
+        const UnbondingDelay: T::BlockNumber = T::UnbondingDelay::get();
+        const ProposalReward: BalanceOf<T> = T::ProposalReward::get();
         const BridgeAccountId: T::AccountId = MODULE_ID.into_account();
+        const RewardsAccountId: T::AccountId = MODULE_ID.into_sub_account(REWARDS_SUB_ACCOUNT);
+        const FeesAccountId: T::AccountId = MODULE_ID.into_sub_account(FEES_SUB_ACCOUNT);
 
         fn deposit_event() = default;
 
+        fn on_initialize(n: T::BlockNumber) -> Weight {
+            let pruned = Self::prune_scheduled_proposals(n);
+            let records = Self::prune_deposit_records(n);
+            T::WeightInfo::prune_proposals(pruned)
+                .saturating_add(T::WeightInfo::prune_deposit_records(records))
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            let weight = migrations::migrate_to_v2::<T>()
+                .saturating_add(migrations::migrate_to_v3::<T>())
+                .saturating_add(migrations::migrate_to_v4::<T>())
+                .saturating_add(migrations::migrate_to_v5::<T>());
+            if let Err(e) = migrations::check_version::<T>() {
+                log::error!(target: "runtime::chainbridge", "{}", e);
+            }
+            weight
+        }
+
         /// Sets the vote threshold for proposals.
         ///
         /// This threshold is used to determine how many votes are required
-        /// before a proposal is executed.
+        /// before a proposal is executed. It can't exceed the number of relayers.
         ///
         /// # <weight>
         /// - O(1) lookup and insert
         /// # </weight>
-        #[weight = 195_000_000]
+        #[weight = T::WeightInfo::set_threshold()]
         pub fn set_threshold(origin, threshold: u32) -> DispatchResult {
             Self::ensure_admin(origin)?;
             Self::set_relayer_threshold(threshold)
         }
 
-        /// Stores a method name on chain under an associated resource ID.
+        /// Sets the proportion of relayers whose votes are required for proposals, replacing
+        /// the absolute threshold. The threshold set by `set_threshold` applies again if `None`.
+        ///
+        /// # <weight>
+        /// - O(1) lookups and insert
+        /// # </weight>
+        #[weight = T::WeightInfo::set_threshold_ratio()]
+        pub fn set_threshold_ratio(origin, ratio: Option<Perbill>) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::set_relayer_threshold_ratio(ratio)
+        }
+
+        /// Stores the description of a resource on chain under its resource ID,
+        /// replacing any previous description.
         ///
         /// # <weight>
         /// - O(1) write
         /// # </weight>
-        #[weight = 195_000_000]
-        pub fn set_resource(origin, id: ResourceId, method: Vec<u8>) -> DispatchResult {
+        #[weight = T::WeightInfo::set_resource()]
+        pub fn set_resource(origin, id: ResourceId, info: ResourceInfo) -> DispatchResult {
             Self::ensure_admin(origin)?;
-            Self::register_resource(id, method)
+            Self::register_resource(id, info)
         }
 
         /// Removes a resource ID from the resource mapping.
@@ -245,7 +785,7 @@ decl_module! {
         /// # <weight>
         /// - O(1) removal
         /// # </weight>
-        #[weight = 195_000_000]
+        #[weight = T::WeightInfo::remove_resource()]
         pub fn remove_resource(origin, id: ResourceId) -> DispatchResult {
             Self::ensure_admin(origin)?;
             Self::unregister_resource(id)
@@ -256,18 +796,94 @@ decl_module! {
         /// # <weight>
         /// - O(1) lookup and insert
         /// # </weight>
-        #[weight = 195_000_000]
+        #[weight = T::WeightInfo::whitelist_chain()]
         pub fn whitelist_chain(origin, id: ChainId) -> DispatchResult {
             Self::ensure_admin(origin)?;
             Self::whitelist(id)
         }
 
-        /// Adds a new relayer to the relayer set.
+        /// Removes a chain ID as a source or destination for bridge transfers.
+        ///
+        /// The deposit nonce of the chain is kept, and is used again if the chain is
+        /// whitelisted later. Pending proposals from the chain can no longer be voted on or
+        /// executed, including after the chain is whitelisted again.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and insert
+        /// # </weight>
+        #[weight = T::WeightInfo::remove_chain()]
+        pub fn remove_chain(origin, id: ChainId) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            Self::unwhitelist(id)
+        }
+
+        /// Sets whether proposals from a chain, transfers to it, or both are permitted.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and insert
+        /// # </weight>
+        #[weight = T::WeightInfo::set_chain_direction()]
+        pub fn set_chain_direction(origin, id: ChainId, direction: ChainDirection) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            ensure!(Self::chain_whitelisted(id), Error::<T>::ChainNotWhitelisted);
+            <ChainDirections>::insert(id, direction);
+            Self::deposit_event(RawEvent::ChainDirectionChanged(id, direction));
+            Ok(())
+        }
+
+        /// Sets the fee charged for transfers of a registered resource to a whitelisted chain.
+        /// A fee of zero makes the transfers free, other fees must be at least the existential
+        /// deposit of `FeeCurrency`, so they can be paid into the fee account.
         ///
         /// # <weight>
         /// - O(1) lookup and insert
         /// # </weight>
-        #[weight = 195_000_000]
+        #[weight = T::WeightInfo::set_fee()]
+        pub fn set_fee(origin, dest_id: ChainId, r_id: ResourceId, fee: FeeBalanceOf<T>) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            ensure!(Self::chain_whitelisted(dest_id), Error::<T>::ChainNotWhitelisted);
+            ensure!(Self::resource_exists(r_id), Error::<T>::ResourceDoesNotExist);
+            ensure!(
+                fee.is_zero() || fee >= T::FeeCurrency::minimum_balance(),
+                Error::<T>::FeeBelowMinimum
+            );
+            if fee.is_zero() {
+                <BridgeFees<T>>::remove((dest_id, r_id));
+            } else {
+                <BridgeFees<T>>::insert((dest_id, r_id), fee);
+            }
+            Self::deposit_event(RawEvent::FeeChanged(dest_id, r_id, fee));
+            Ok(())
+        }
+
+        /// Transfers fees collected from outbound transfers out of the fee account.
+        /// The fee account is kept alive.
+        ///
+        /// # <weight>
+        /// - O(1) transfer
+        /// # </weight>
+        #[weight = T::WeightInfo::withdraw_fees()]
+        pub fn withdraw_fees(origin, to: T::AccountId, amount: FeeBalanceOf<T>) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            T::FeeCurrency::transfer(
+                &Self::fees_account_id(),
+                &to,
+                amount,
+                ExistenceRequirement::KeepAlive,
+            )?;
+            Self::deposit_event(RawEvent::FeesWithdrawn(to, amount));
+            Ok(())
+        }
+
+        /// Adds a new relayer to the relayer set.
+        ///
+        /// Fails if the slots of all removed relayers still have votes on existing proposals.
+        ///
+        /// # <weight>
+        /// - O(M) lookup of a free slot, where M is `MaxRelayers`
+        /// - O(1) insert
+        /// # </weight>
+        #[weight = T::WeightInfo::add_relayer(T::MaxRelayers::get())]
         pub fn add_relayer(origin, v: T::AccountId) -> DispatchResult {
             Self::ensure_admin(origin)?;
             Self::register_relayer(v)
@@ -278,119 +894,743 @@ decl_module! {
         /// # <weight>
         /// - O(1) lookup and removal
         /// # </weight>
-        #[weight = 195_000_000]
+        #[weight = T::WeightInfo::remove_relayer()]
         pub fn remove_relayer(origin, v: T::AccountId) -> DispatchResult {
             Self::ensure_admin(origin)?;
             Self::unregister_relayer(v)
         }
 
-        /// Commits a vote in favour of the provided proposal.
-        ///
-        /// If a proposal with the given nonce and source chain ID does not already exist, it will
-        /// be created with an initial vote in favour from the caller.
+        /// Sets the key the calling relayer signs proposals with off-chain.
         ///
         /// # <weight>
-        /// - weight of proposed call, regardless of whether execution is performed
+        /// - O(1) write
         /// # </weight>
-        #[weight = (call.get_dispatch_info().weight + 195_000_000, call.get_dispatch_info().class, Pays::Yes)]
-        pub fn acknowledge_proposal(origin, nonce: DepositNonce, src_id: ChainId, r_id: ResourceId, call: Box<<T as Config>::Proposal>) -> DispatchResult {
+        #[weight = T::WeightInfo::set_relayer_key()]
+        pub fn set_relayer_key(origin, key: MultiSigner) -> DispatchResult {
             let who = ensure_signed(origin)?;
             ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
-            ensure!(Self::chain_whitelisted(src_id), Error::<T>::ChainNotWhitelisted);
-            ensure!(Self::resource_exists(r_id), Error::<T>::ResourceDoesNotExist);
-
-            Self::vote_for(who, nonce, src_id, call)
+            <RelayerKeys<T>>::insert(&who, key);
+            Self::deposit_event(RawEvent::RelayerKeySet(who));
+            Ok(())
         }
 
-        /// Commits a vote against a provided proposal.
+        /// Releases the bond of a removed relayer once the `unbonding_period` has passed.
         ///
         /// # <weight>
-        /// - Fixed, since execution of proposal should not be included
+        /// - O(1) lookup and removal
         /// # </weight>
-        #[weight = 195_000_000]
-        pub fn reject_proposal(origin, nonce: DepositNonce, src_id: ChainId, r_id: ResourceId, call: Box<<T as Config>::Proposal>) -> DispatchResult {
+        #[weight = T::WeightInfo::withdraw_unbonded()]
+        pub fn withdraw_unbonded(origin) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
-            ensure!(Self::chain_whitelisted(src_id), Error::<T>::ChainNotWhitelisted);
-            ensure!(Self::resource_exists(r_id), Error::<T>::ResourceDoesNotExist);
-
-            Self::vote_against(who, nonce, src_id, call)
+            let bond = Self::bond(&who).ok_or(Error::<T>::NotBonded)?;
+            let until = bond.until.ok_or(Error::<T>::RelayerStillRegistered)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(
+                until.saturating_add(Self::unbonding_period()) <= now,
+                Error::<T>::UnbondingNotFinished
+            );
+
+            T::Currency::unreserve(&who, bond.amount);
+            <Bonds<T>>::remove(&who);
+            Self::deposit_event(RawEvent::BondWithdrawn(who, bond.amount));
+            Ok(())
         }
 
-        /// Evaluate the state of a proposal given the current vote threshold.
+        /// Reports a relayer that voted in favour of a proposal conflicting with the proposal
+        /// executed for the same deposit, and slashes its bond.
         ///
-        /// A proposal with enough votes will be either executed or cancelled, and the status
-        /// will be updated accordingly.
+        /// Votes are recorded by slot. A freed slot is only reassigned once the proposals its
+        /// relayer may have voted on have been removed, so only proposals created before the
+        /// relayer was removed can be reported.
+        ///
+        /// Only call proposals conflict with an executed call proposal, and deposit proposals
+        /// with an executed deposit proposal, as relayers may vote on a deposit in either form.
+        /// Reports are accepted until the executed proposal is removed.
         ///
         /// # <weight>
-        /// - weight of proposed call, regardless of whether execution is performed
+        /// - O(1) lookups and slash
         /// # </weight>
-        #[weight = (prop.get_dispatch_info().weight + 195_000_000, prop.get_dispatch_info().class, Pays::Yes)]
-        pub fn eval_vote_state(origin, nonce: DepositNonce, src_id: ChainId, prop: Box<<T as Config>::Proposal>) -> DispatchResult {
+        #[weight = T::WeightInfo::report_misbehaviour()]
+        pub fn report_misbehaviour(origin, relayer: T::AccountId, src_id: ChainId, nonce: DepositNonce, prop_hash: T::Hash) -> DispatchResult {
             ensure_signed(origin)?;
+            let bond = Self::bond(&relayer).ok_or(Error::<T>::NotBonded)?;
+            ensure!(!bond.amount.is_zero(), Error::<T>::NotBonded);
+            let executed = Self::executed_nonce(src_id, nonce).ok_or(Error::<T>::InvalidReport)?;
+            ensure!(executed != prop_hash, Error::<T>::InvalidReport);
+            ensure!(
+                <Proposals<T>>::contains_key(src_id, (nonce, executed)),
+                Error::<T>::InvalidReport
+            );
+            // Calls are stored while their proposals exist, deposits are never stored
+            ensure!(
+                <ProposalCalls<T>>::contains_key(executed) == <ProposalCalls<T>>::contains_key(prop_hash),
+                Error::<T>::InvalidReport
+            );
+            let votes = Self::votes(src_id, (nonce, prop_hash)).ok_or(Error::<T>::ProposalDoesNotExist)?;
+            ensure!(votes.votes_for.contains(bond.slot), Error::<T>::InvalidReport);
+            let created = votes.expiry.saturating_sub(T::ProposalLifetime::get());
+            ensure!(
+                bond.until.map_or(true, |until| created <= until),
+                Error::<T>::InvalidReport
+            );
+
+            let amount = bond.amount;
+            let (imbalance, _) = T::Currency::slash_reserved(&relayer, amount);
+            T::Slash::on_unbalanced(imbalance);
+            <Bonds<T>>::insert(&relayer, Bond { amount: Zero::zero(), ..bond });
+            Self::deposit_event(RawEvent::RelayerSlashed(relayer, amount));
+            Ok(())
+        }
 
-            Self::try_resolve_proposal(nonce, src_id, prop)
+        /// Pays out the share of the reward pot corresponding to the reward points of the caller.
+        ///
+        /// # <weight>
+        /// - O(1) lookups and transfer
+        /// # </weight>
+        #[weight = T::WeightInfo::claim_rewards()]
+        pub fn claim_rewards(origin) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let points = Self::reward_points(&who);
+            ensure!(points > 0, Error::<T>::NoRewards);
+
+            let pot = Self::rewards_account_id();
+            let share = Perbill::from_rational_approximation(points, Self::total_reward_points());
+            let amount = share.mul_floor(T::Currency::free_balance(&pot));
+            T::Currency::transfer(&pot, &who, amount, ExistenceRequirement::AllowDeath)?;
+            <RewardPoints<T>>::remove(&who);
+            <TotalRewardPoints>::mutate(|total| *total = total.saturating_sub(points));
+            Self::deposit_event(RawEvent::RewardsClaimed(who, amount));
+            Ok(())
         }
-    }
-}
 
-impl<T: Config> Module<T> {
-    // *** Utility methods ***
+        /// Halts all bridge transfers and proposals.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[weight = T::WeightInfo::pause_bridge()]
+        pub fn pause_bridge(origin) -> DispatchResult {
+            Self::ensure_pause_origin(origin)?;
+            <BridgePaused>::put(true);
+            Self::deposit_event(RawEvent::BridgePaused);
+            Ok(())
+        }
 
-    pub fn ensure_admin(o: T::Origin) -> DispatchResult {
-        T::AdminOrigin::try_origin(o)
-            .map(|_| ())
-            .or_else(ensure_root)?;
-        Ok(())
-    }
+        /// Resumes bridge transfers and proposals.
+        ///
+        /// # <weight>
+        /// - O(1) write
+        /// # </weight>
+        #[weight = T::WeightInfo::unpause_bridge()]
+        pub fn unpause_bridge(origin) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            <BridgePaused>::put(false);
+            Self::deposit_event(RawEvent::BridgeUnpaused);
+            Ok(())
+        }
 
-    /// Checks if who is a relayer
-    pub fn is_relayer(who: &T::AccountId) -> bool {
-        Self::relayers(who)
-    }
+        /// Halts transfers to and proposals from a whitelisted chain.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and insert
+        /// # </weight>
+        #[weight = T::WeightInfo::pause_chain()]
+        pub fn pause_chain(origin, id: ChainId) -> DispatchResult {
+            Self::ensure_pause_origin(origin)?;
+            ensure!(Self::chain_whitelisted(id), Error::<T>::ChainNotWhitelisted);
+            <PausedChains>::insert(id, true);
+            Self::deposit_event(RawEvent::ChainPaused(id));
+            Ok(())
+        }
 
-    /// Provides an AccountId for the pallet.
-    /// This is used both as an origin check and deposit/withdrawal account.
-    pub fn account_id() -> T::AccountId {
-        MODULE_ID.into_account()
-    }
+        /// Resumes transfers to and proposals from a chain.
+        ///
+        /// # <weight>
+        /// - O(1) removal
+        /// # </weight>
+        #[weight = T::WeightInfo::unpause_chain()]
+        pub fn unpause_chain(origin, id: ChainId) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            <PausedChains>::remove(id);
+            Self::deposit_event(RawEvent::ChainUnpaused(id));
+            Ok(())
+        }
 
-    /// Asserts if a resource is registered
-    pub fn resource_exists(id: ResourceId) -> bool {
-        return Self::resources(id) != None;
-    }
+        /// Removes up to `limit` completed proposals made before V2.
+        ///
+        /// These proposals are kept by the storage migration so they can't be executed again.
+        /// They should be removed once the deposits they executed can no longer be relayed, after
+        /// which new proposals are no longer checked against them.
+        ///
+        /// # <weight>
+        /// - O(L) removals, where L is `limit`
+        /// # </weight>
+        #[weight = T::WeightInfo::purge_legacy_votes(*limit)]
+        pub fn purge_legacy_votes(origin, limit: u32) -> DispatchResult {
+            Self::ensure_admin(origin)?;
+            let removed = migrations::purge_legacy_votes::<T>(limit);
+            Self::deposit_event(RawEvent::LegacyVotesPurged(removed));
+            Ok(())
+        }
 
-    /// Checks if a chain exists as a whitelisted destination
-    pub fn chain_whitelisted(id: ChainId) -> bool {
-        return Self::chains(id) != None;
-    }
+        /// Commits a vote in favour of the provided proposal.
+        ///
+        /// If a proposal with the given nonce and source chain ID does not already exist, it will
+        /// be created with an initial vote in favour from the caller. The call must be accepted
+        /// by the `ProposalValidator` for the resource.
+        ///
+        /// Successful votes are free for the relayer.
+        ///
+        /// # <weight>
+        /// - weight of proposed call, refunded if the proposal is not executed
+        /// - O(R) vote lookup, where R is the number of relayers
+        /// # </weight>
+        #[weight = (
+            call.get_dispatch_info().weight
+                .saturating_add(T::WeightInfo::acknowledge_proposal(<RelayerCount>::get())),
+            call.get_dispatch_info().class,
+            Pays::Yes
+        )]
+        pub fn acknowledge_proposal(origin, nonce: DepositNonce, src_id: ChainId, r_id: ResourceId, call: Box<<T as Config>::Proposal>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
 
-    /// Increments the deposit nonce for the specified chain ID
-    fn bump_nonce(id: ChainId) -> DepositNonce {
-        let nonce = Self::chains(id).unwrap_or_default() + 1;
-        <ChainNonces>::insert(id, nonce);
-        nonce
-    }
+            let executed = Self::acknowledge(who, nonce, src_id, r_id, call)?;
+            Ok(Self::vote_post_info(
+                T::WeightInfo::acknowledge_proposal(<RelayerCount>::get()),
+                executed,
+                Pays::No,
+            ))
+        }
 
-    // *** Admin methods ***
+        /// Commits a vote against a provided proposal.
+        ///
+        /// Successful votes are free for the relayer.
+        ///
+        /// # <weight>
+        /// - O(R) vote lookup, where R is the number of relayers
+        /// - execution of proposal is not included
+        /// # </weight>
+        #[weight = T::WeightInfo::reject_proposal(<RelayerCount>::get())]
+        pub fn reject_proposal(origin, nonce: DepositNonce, src_id: ChainId, r_id: ResourceId, call: Box<<T as Config>::Proposal>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
 
-    /// Set a new voting threshold
-    pub fn set_relayer_threshold(threshold: u32) -> DispatchResult {
-        ensure!(threshold > 0, Error::<T>::InvalidThreshold);
-        <RelayerThreshold>::put(threshold);
-        Self::deposit_event(RawEvent::RelayerThresholdChanged(threshold));
-        Ok(())
-    }
+            let executed = Self::reject(who, nonce, src_id, r_id, call)?;
+            Ok(Self::vote_post_info(
+                T::WeightInfo::reject_proposal(<RelayerCount>::get()),
+                executed,
+                Pays::No,
+            ))
+        }
 
-    /// Register a method for a resource Id, enabling associated transfers
-    pub fn register_resource(id: ResourceId, method: Vec<u8>) -> DispatchResult {
-        <Resources>::insert(id, method);
-        Ok(())
-    }
+        /// Commits votes in favour of several proposals of `(nonce, src_id, r_id, call)`.
+        ///
+        /// Each vote is committed as by `acknowledge_proposal`, a failed vote doesn't affect the
+        /// others. The result of each vote is signalled by `BatchVoteSucceeded` or
+        /// `BatchVoteFailed`.
+        ///
+        /// The batch is free for the relayer if every vote succeeds.
+        ///
+        /// # <weight>
+        /// - weight of the proposed calls, refunded if the proposals are not executed
+        /// - O(B * R) vote lookups, where B is the size of the batch and R the number of relayers
+        /// # </weight>
+        #[weight = proposals.iter().fold(
+            T::WeightInfo::acknowledge_proposals_batch(<RelayerCount>::get(), proposals.len() as u32),
+            |weight, (_, _, _, call)| weight.saturating_add(call.get_dispatch_info().weight),
+        )]
+        pub fn acknowledge_proposals_batch(origin, proposals: Vec<(DepositNonce, ChainId, ResourceId, <T as Config>::Proposal)>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
+            ensure!(proposals.len() as u32 <= T::MaxBatchSize::get(), Error::<T>::BatchTooLarge);
+
+            let weight = T::WeightInfo::acknowledge_proposals_batch(<RelayerCount>::get(), proposals.len() as u32);
+            Ok(Self::vote_batch(
+                proposals,
+                weight,
+                |nonce, src_id, r_id, call| Self::acknowledge(who.clone(), nonce, src_id, r_id, call),
+            ))
+        }
+
+        /// Commits votes against several proposals of `(nonce, src_id, r_id, call)`.
+        ///
+        /// Each vote is committed as by `reject_proposal`, a failed vote doesn't affect the
+        /// others. The result of each vote is signalled by `BatchVoteSucceeded` or
+        /// `BatchVoteFailed`.
+        ///
+        /// The batch is free for the relayer if every vote succeeds.
+        ///
+        /// # <weight>
+        /// - O(B * R) vote lookups, where B is the size of the batch and R the number of relayers
+        /// - execution of proposals is not included
+        /// # </weight>
+        #[weight = T::WeightInfo::reject_proposals_batch(<RelayerCount>::get(), proposals.len() as u32)]
+        pub fn reject_proposals_batch(origin, proposals: Vec<(DepositNonce, ChainId, ResourceId, <T as Config>::Proposal)>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
+            ensure!(proposals.len() as u32 <= T::MaxBatchSize::get(), Error::<T>::BatchTooLarge);
+
+            let weight = T::WeightInfo::reject_proposals_batch(<RelayerCount>::get(), proposals.len() as u32);
+            Ok(Self::vote_batch(
+                proposals,
+                weight,
+                |nonce, src_id, r_id, call| Self::reject(who.clone(), nonce, src_id, r_id, call),
+            ))
+        }
+
+        /// Commits the votes of several relayers in favour of a proposal and executes it.
+        ///
+        /// Each relayer signs the `signed_proposal_payload` of the proposal with the key set by
+        /// `set_relayer_key`. Together with the votes already committed, the signatures must
+        /// reach the vote threshold. Any account may submit the signatures.
+        ///
+        /// # <weight>
+        /// - weight of proposed call, refunded if the proposal is not executed
+        /// - O(S) signature verifications, where S is the number of signatures
+        /// # </weight>
+        #[weight = (
+            call.get_dispatch_info().weight
+                .saturating_add(T::WeightInfo::submit_signed_proposal(signatures.len() as u32)),
+            call.get_dispatch_info().class,
+            Pays::Yes
+        )]
+        pub fn submit_signed_proposal(origin, nonce: DepositNonce, src_id: ChainId, r_id: ResourceId, call: Box<<T as Config>::Proposal>, signatures: Vec<(T::AccountId, MultiSignature)>) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            Self::ensure_inbound(src_id)?;
+            let info = Self::ensure_resource(r_id, src_id)?;
+            ensure!(
+                T::ProposalValidator::validate(r_id, &info, &call),
+                Error::<T>::InvalidProposal
+            );
+
+            let executed = Self::vote_signed(nonce, src_id, r_id, call, &signatures)?;
+            Ok(Self::vote_post_info(
+                T::WeightInfo::submit_signed_proposal(signatures.len() as u32),
+                executed,
+                Pays::Yes,
+            ))
+        }
+
+        /// Commits a vote in favour of a deposit made on another chain.
+        ///
+        /// Relayers vote on the hash of the resource ID and deposit data. Once the vote threshold
+        /// is met, the data is decoded according to the kind of the resource and executed by the
+        /// `ResourceHandler`.
+        ///
+        /// Successful votes are free for the relayer.
+        ///
+        /// # <weight>
+        /// - weight of the resource handler, refunded if the deposit is not executed
+        /// - O(R) vote lookup, where R is the number of relayers
+        /// # </weight>
+        #[weight = T::ResourceHandler::weight(*r_id, data)
+            .saturating_add(T::WeightInfo::acknowledge_deposit(<RelayerCount>::get()))]
+        pub fn acknowledge_deposit(origin, nonce: DepositNonce, src_id: ChainId, r_id: ResourceId, data: Vec<u8>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
+            Self::ensure_inbound(src_id)?;
+            let info = Self::ensure_resource(r_id, src_id)?;
+            ensure!(
+                Deposit::parse(info.kind, &data).is_some(),
+                Error::<T>::InvalidDepositData
+            );
+
+            let executed = Self::vote(who, nonce, src_id, ProposalContent::Deposit(r_id, data), true)?;
+            Ok(Self::vote_post_info(
+                T::WeightInfo::acknowledge_deposit(<RelayerCount>::get()),
+                executed,
+                Pays::No,
+            ))
+        }
+
+        /// Commits a vote against a deposit made on another chain.
+        ///
+        /// Successful votes are free for the relayer.
+        ///
+        /// # <weight>
+        /// - O(R) vote lookup, where R is the number of relayers
+        /// # </weight>
+        #[weight = T::WeightInfo::reject_deposit(<RelayerCount>::get())]
+        pub fn reject_deposit(origin, nonce: DepositNonce, src_id: ChainId, r_id: ResourceId, data: Vec<u8>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
+            Self::ensure_inbound(src_id)?;
+            Self::ensure_resource(r_id, src_id)?;
+
+            let executed = Self::vote(who, nonce, src_id, ProposalContent::Deposit(r_id, data), false)?;
+            Ok(Self::vote_post_info(
+                T::WeightInfo::reject_deposit(<RelayerCount>::get()),
+                executed,
+                Pays::No,
+            ))
+        }
+
+        /// Evaluate the state of a proposal given the current vote threshold.
+        ///
+        /// A proposal with enough votes will be either executed or cancelled, and the status
+        /// will be updated accordingly.
+        ///
+        /// # <weight>
+        /// - weight of proposed call, refunded if the proposal is not executed
+        /// - O(R) vote lookup, where R is the number of relayers
+        /// # </weight>
+        #[weight = (
+            prop.get_dispatch_info().weight
+                .saturating_add(T::WeightInfo::eval_vote_state(<RelayerCount>::get())),
+            prop.get_dispatch_info().class,
+            Pays::Yes
+        )]
+        pub fn eval_vote_state(origin, nonce: DepositNonce, src_id: ChainId, prop: Box<<T as Config>::Proposal>) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            Self::ensure_inbound(src_id)?;
+
+            let executed = Self::try_resolve_proposal(nonce, src_id, ProposalContent::Call(prop))?;
+            Ok(Self::vote_post_info(
+                T::WeightInfo::eval_vote_state(<RelayerCount>::get()),
+                executed,
+                Pays::Yes,
+            ))
+        }
+
+        /// Evaluate the state of a deposit proposal given the current vote threshold.
+        ///
+        /// A proposal with enough votes will be either executed or cancelled, and the status
+        /// will be updated accordingly.
+        ///
+        /// # <weight>
+        /// - weight of the resource handler, refunded if the deposit is not executed
+        /// - O(R) vote lookup, where R is the number of relayers
+        /// # </weight>
+        #[weight = T::ResourceHandler::weight(*r_id, data)
+            .saturating_add(T::WeightInfo::eval_deposit_state(<RelayerCount>::get()))]
+        pub fn eval_deposit_state(origin, nonce: DepositNonce, src_id: ChainId, r_id: ResourceId, data: Vec<u8>) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            Self::ensure_inbound(src_id)?;
+
+            let executed = Self::try_resolve_proposal(nonce, src_id, ProposalContent::Deposit(r_id, data))?;
+            Ok(Self::vote_post_info(
+                T::WeightInfo::eval_deposit_state(<RelayerCount>::get()),
+                executed,
+                Pays::Yes,
+            ))
+        }
+
+        /// Removes a proposal whose retention period has passed.
+        ///
+        /// Proposals are normally removed automatically, this allows anyone to remove those
+        /// that were never scheduled for removal. The call is free if successful.
+        ///
+        /// # <weight>
+        /// - O(1) lookup and removal
+        /// # </weight>
+        #[weight = T::WeightInfo::clean_proposal()]
+        pub fn clean_proposal(origin, src_id: ChainId, nonce: DepositNonce, prop_hash: T::Hash) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            let votes = Self::votes(src_id, (nonce, prop_hash)).ok_or(Error::<T>::ProposalDoesNotExist)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            ensure!(Self::is_prunable(&votes, now), Error::<T>::ProposalNotPrunable);
+
+            Self::remove_proposal(src_id, nonce, prop_hash, &votes);
+            Ok(Pays::No.into())
+        }
+
+        /// Retries the execution of an approved proposal whose call failed.
+        ///
+        /// Each proposal can be retried at most `MaxProposalRetries` times.
+        ///
+        /// # <weight>
+        /// - weight of proposed call
+        /// - O(1) lookup and update
+        /// # </weight>
+        #[weight = (
+            call.get_dispatch_info().weight
+                .saturating_add(T::WeightInfo::retry_proposal()),
+            call.get_dispatch_info().class,
+            Pays::Yes
+        )]
+        pub fn retry_proposal(origin, nonce: DepositNonce, src_id: ChainId, call: Box<<T as Config>::Proposal>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
+            Self::ensure_inbound(src_id)?;
+
+            let executed = Self::retry_execution(nonce, src_id, ProposalContent::Call(call))?;
+            Ok(Self::vote_post_info(T::WeightInfo::retry_proposal(), Some(executed), Pays::Yes))
+        }
+
+        /// Retries the execution of an approved deposit whose handler failed.
+        ///
+        /// Each deposit can be retried at most `MaxProposalRetries` times.
+        ///
+        /// # <weight>
+        /// - weight of the resource handler
+        /// - O(1) lookup and update
+        /// # </weight>
+        #[weight = T::ResourceHandler::weight(*r_id, data)
+            .saturating_add(T::WeightInfo::retry_deposit())]
+        pub fn retry_deposit(origin, nonce: DepositNonce, src_id: ChainId, r_id: ResourceId, data: Vec<u8>) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(Self::is_relayer(&who), Error::<T>::MustBeRelayer);
+            Self::ensure_inbound(src_id)?;
+
+            let executed = Self::retry_execution(nonce, src_id, ProposalContent::Deposit(r_id, data))?;
+            Ok(Self::vote_post_info(T::WeightInfo::retry_deposit(), Some(executed), Pays::Yes))
+        }
+    }
+}
+
+impl<T: Config> Module<T> {
+    // *** Utility methods ***
+
+    pub fn ensure_admin(o: T::Origin) -> DispatchResult {
+        T::AdminOrigin::try_origin(o)
+            .map(|_| ())
+            .or_else(ensure_root)?;
+        Ok(())
+    }
+
+    /// Ensures the origin may pause the bridge, either through `PauseOrigin` or as admin
+    pub fn ensure_pause_origin(o: T::Origin) -> DispatchResult {
+        T::PauseOrigin::try_origin(o)
+            .map(|_| ())
+            .or_else(Self::ensure_admin)
+    }
+
+    /// Checks if transfers and proposals involving a chain are halted
+    pub fn is_paused(id: ChainId) -> bool {
+        Self::bridge_paused() || Self::chain_paused(id)
+    }
+
+    /// Computes the hash under which a proposal is stored
+    pub fn proposal_hash(prop: &T::Proposal) -> T::Hash {
+        T::Hashing::hash_of(prop)
+    }
+
+    /// Computes the hash under which a deposit proposal is stored
+    pub fn deposit_hash(r_id: ResourceId, data: &[u8]) -> T::Hash {
+        T::Hashing::hash_of(&(r_id, data))
+    }
+
+    /// Computes the message relayers sign to vote for a proposal with `submit_signed_proposal`.
+    /// It includes the ID of this chain, so signatures can't be replayed on other chains.
+    pub fn signed_proposal_payload(
+        src_id: ChainId,
+        nonce: DepositNonce,
+        r_id: ResourceId,
+        prop_hash: T::Hash,
+    ) -> Vec<u8> {
+        (
+            MODULE_ID.0,
+            T::ChainId::get(),
+            src_id,
+            nonce,
+            r_id,
+            prop_hash,
+        )
+            .encode()
+    }
+
+    /// Computes the hash under which a call or deposit proposal is stored
+    fn content_hash(content: &ProposalContent<T::Proposal>) -> T::Hash {
+        match content {
+            ProposalContent::Call(call) => Self::proposal_hash(call),
+            ProposalContent::Deposit(r_id, data) => Self::deposit_hash(*r_id, data),
+        }
+    }
+
+    /// Returns the threshold currently in effect
+    pub fn vote_threshold() -> VoteThreshold {
+        Self::relayer_threshold_ratio().map_or(
+            VoteThreshold::Absolute(Self::relayer_threshold()),
+            VoteThreshold::Proportion,
+        )
+    }
+
+    /// Returns the number of votes currently required for a proposal to execute
+    pub fn required_votes() -> u32 {
+        Self::vote_threshold().votes(Self::relayer_count())
+    }
+
+    /// Number of blocks after the removal of a relayer until its bond can be withdrawn.
+    /// Covers the lifetime and retention of the proposals the relayer voted on, so they can
+    /// be reported while the bond is held.
+    pub fn unbonding_period() -> T::BlockNumber {
+        T::UnbondingDelay::get()
+            .max(T::ProposalLifetime::get().saturating_add(T::ProposalRetention::get()))
+    }
+
+    /// Checks if who is a relayer
+    pub fn is_relayer(who: &T::AccountId) -> bool {
+        <Relayers<T>>::contains_key(who)
+    }
+
+    /// Returns the current relayer set
+    pub fn relayer_list() -> Vec<T::AccountId> {
+        <Relayers<T>>::iter().map(|(relayer, _)| relayer).collect()
+    }
+
+    /// Returns the current relayers along with their slots
+    pub fn relayer_slots() -> Vec<(T::AccountId, u32)> {
+        <Relayers<T>>::iter().collect()
+    }
+
+    /// Returns all whitelisted chains and their deposit nonce
+    pub fn whitelisted_chains() -> Vec<(ChainId, DepositNonce)> {
+        <ChainNonces>::iter()
+            .filter(|(id, _)| !Self::chain_removed(id))
+            .collect()
+    }
+
+    /// Returns the IDs of all registered resources
+    pub fn resource_ids() -> Vec<ResourceId> {
+        <Resources>::iter().map(|(id, _)| id).collect()
+    }
+
+    /// Returns the votes of the proposal for a call from a source chain
+    pub fn proposal_votes(
+        src_id: ChainId,
+        nonce: DepositNonce,
+        prop: &T::Proposal,
+    ) -> Option<ProposalVotes<T::BlockNumber>> {
+        Self::votes(src_id, (nonce, Self::proposal_hash(prop)))
+    }
+
+    /// Checks if a relayer may currently vote on the proposal for a call from a source chain
+    pub fn can_vote(
+        who: &T::AccountId,
+        src_id: ChainId,
+        nonce: DepositNonce,
+        prop: &T::Proposal,
+    ) -> bool {
+        let now = <frame_system::Pallet<T>>::block_number();
+        let (votes, _) = Self::votes_or_new(src_id, nonce, Self::proposal_hash(prop), now);
+        match Self::relayer_slot(who) {
+            Some(slot) => {
+                Self::ensure_inbound(src_id).is_ok()
+                    && Self::ensure_can_vote(slot, src_id, nonce, &votes, now).is_ok()
+            }
+            None => false,
+        }
+    }
+
+    /// Provides an AccountId for the pallet.
+    /// This is used both as an origin check and deposit/withdrawal account.
+    pub fn account_id() -> T::AccountId {
+        MODULE_ID.into_account()
+    }
+
+    /// Account holding the rewards of relayers, see `claim_rewards`
+    pub fn rewards_account_id() -> T::AccountId {
+        MODULE_ID.into_sub_account(REWARDS_SUB_ACCOUNT)
+    }
+
+    /// Account collecting the fees of outbound transfers, see `withdraw_fees`
+    pub fn fees_account_id() -> T::AccountId {
+        MODULE_ID.into_sub_account(FEES_SUB_ACCOUNT)
+    }
+
+    /// Asserts if a resource is registered
+    pub fn resource_exists(id: ResourceId) -> bool {
+        return Self::resources(id) != None;
+    }
+
+    /// Ensures a resource is registered, enabled and may be bridged with the chain
+    fn ensure_resource(id: ResourceId, chain_id: ChainId) -> Result<ResourceInfo, DispatchError> {
+        let info = Self::resources(id).ok_or(Error::<T>::ResourceDoesNotExist)?;
+        ensure!(info.enabled, Error::<T>::ResourceDisabled);
+        ensure!(
+            info.allows_chain(chain_id),
+            Error::<T>::ResourceChainNotAllowed
+        );
+        Ok(info)
+    }
+
+    /// Checks if a chain exists as a whitelisted destination
+    pub fn chain_whitelisted(id: ChainId) -> bool {
+        return Self::chains(id) != None && !Self::chain_removed(id);
+    }
+
+    /// Ensures proposals from a chain are currently permitted
+    fn ensure_inbound(src_id: ChainId) -> DispatchResult {
+        ensure!(
+            Self::chain_whitelisted(src_id),
+            Error::<T>::ChainNotWhitelisted
+        );
+        ensure!(!Self::is_paused(src_id), Error::<T>::BridgePaused);
+        ensure!(
+            Self::chain_direction(src_id).allows_inbound(),
+            Error::<T>::DirectionNotAllowed
+        );
+        Ok(())
+    }
+
+    /// Ensures transfers to a chain are currently permitted
+    fn ensure_outbound(dest_id: ChainId) -> DispatchResult {
+        ensure!(
+            Self::chain_whitelisted(dest_id),
+            Error::<T>::ChainNotWhitelisted
+        );
+        ensure!(!Self::is_paused(dest_id), Error::<T>::BridgePaused);
+        ensure!(
+            Self::chain_direction(dest_id).allows_outbound(),
+            Error::<T>::DirectionNotAllowed
+        );
+        Ok(())
+    }
+
+    /// Increments the deposit nonce for the specified chain ID
+    fn bump_nonce(id: ChainId) -> DepositNonce {
+        let nonce = Self::chains(id).unwrap_or_default() + 1;
+        <ChainNonces>::insert(id, nonce);
+        nonce
+    }
+
+    // *** Admin methods ***
+
+    /// Set a new voting threshold
+    pub fn set_relayer_threshold(threshold: u32) -> DispatchResult {
+        ensure!(threshold > 0, Error::<T>::InvalidThreshold);
+        ensure!(
+            threshold <= Self::relayer_count(),
+            Error::<T>::ThresholdExceedsRelayers
+        );
+        <RelayerThreshold>::put(threshold);
+        Self::deposit_event(RawEvent::RelayerThresholdChanged(threshold));
+        Ok(())
+    }
+
+    /// Sets or clears the proportional voting threshold
+    pub fn set_relayer_threshold_ratio(ratio: Option<Perbill>) -> DispatchResult {
+        let threshold = match ratio {
+            Some(ratio) => {
+                ensure!(!ratio.is_zero(), Error::<T>::InvalidThreshold);
+                VoteThreshold::Proportion(ratio)
+            }
+            None => VoteThreshold::Absolute(<RelayerThreshold>::get()),
+        };
+        let count = Self::relayer_count();
+        ensure!(
+            threshold.votes(count) <= count,
+            Error::<T>::ThresholdExceedsRelayers
+        );
+        <RelayerThresholdRatio>::set(ratio);
+        Self::deposit_event(RawEvent::RelayerThresholdRatioChanged(ratio));
+        Ok(())
+    }
+
+    /// Register the description of a resource Id, enabling associated transfers
+    pub fn register_resource(id: ResourceId, info: ResourceInfo) -> DispatchResult {
+        let kind = info.kind;
+        <Resources>::insert(id, info);
+        Self::deposit_event(RawEvent::ResourceRegistered(id, kind));
+        Ok(())
+    }
 
     /// Removes a resource ID, disabling associated transfer
     pub fn unregister_resource(id: ResourceId) -> DispatchResult {
+        ensure!(Self::resource_exists(id), Error::<T>::ResourceDoesNotExist);
         <Resources>::remove(id);
+        Self::deposit_event(RawEvent::ResourceRemoved(id));
         Ok(())
     }
 
@@ -403,28 +1643,94 @@ impl<T: Config> Module<T> {
             !Self::chain_whitelisted(id),
             Error::<T>::ChainAlreadyWhitelisted
         );
-        <ChainNonces>::insert(&id, 0);
+        // A previously removed chain continues with its deposit nonce
+        if Self::chain_removed(id) {
+            <RemovedChains>::remove(&id);
+        } else {
+            <ChainNonces>::insert(&id, 0);
+        }
         Self::deposit_event(RawEvent::ChainWhitelisted(id));
         Ok(())
     }
 
-    /// Adds a new relayer to the set
+    /// Removes a whitelisted chain ID, keeping its deposit nonce
+    pub fn unwhitelist(id: ChainId) -> DispatchResult {
+        ensure!(Self::chain_whitelisted(id), Error::<T>::ChainNotWhitelisted);
+        <RemovedChains>::insert(&id, true);
+        <ChainRemovals<T>>::insert(&id, <frame_system::Pallet<T>>::block_number());
+        <ChainDirections>::remove(&id);
+        <PausedChains>::remove(&id);
+        Self::deposit_event(RawEvent::ChainRemoved(id));
+        Ok(())
+    }
+
+    /// Adds a new relayer to the set and reserves its bond
     pub fn register_relayer(relayer: T::AccountId) -> DispatchResult {
         ensure!(
             !Self::is_relayer(&relayer),
             Error::<T>::RelayerAlreadyExists
         );
-        <Relayers<T>>::insert(&relayer, true);
+        ensure!(
+            Self::relayer_count() < T::MaxRelayers::get(),
+            Error::<T>::TooManyRelayers
+        );
+        let now = <frame_system::Pallet<T>>::block_number();
+        let slot = Self::free_slot(now).ok_or(Error::<T>::SlotCoolingDown)?;
+        // A relayer added again before withdrawing its bond only tops it up
+        let reserved = Self::bond(&relayer).map_or_else(Zero::zero, |bond| bond.amount);
+        T::Currency::reserve(&relayer, T::RelayerBond::get().saturating_sub(reserved))?;
+
+        <OccupiedSlots>::mutate(|slots| slots.insert(slot));
+        <FreedSlots<T>>::remove(slot);
+        <Relayers<T>>::insert(&relayer, slot);
+        let bond = Bond {
+            amount: reserved.max(T::RelayerBond::get()),
+            slot,
+            since: now,
+            until: None,
+        };
+        <Bonds<T>>::insert(&relayer, bond);
         <RelayerCount>::mutate(|i| *i += 1);
 
         Self::deposit_event(RawEvent::RelayerAdded(relayer));
         Ok(())
     }
 
-    /// Removes a relayer from the set
+    /// Returns the lowest slot below `MaxRelayers` that isn't assigned and whose votes by its
+    /// previous relayer have all been removed, along with their proposals.
+    fn free_slot(now: T::BlockNumber) -> Option<u32> {
+        let occupied = Self::occupied_slots();
+        let cooldown = T::ProposalLifetime::get().saturating_add(T::ProposalRetention::get());
+        (0..T::MaxRelayers::get()).find(|slot| {
+            !occupied.contains(*slot)
+                && Self::slot_freed_at(slot)
+                    .map_or(true, |freed| freed.saturating_add(cooldown) <= now)
+        })
+    }
+
+    /// Removes a relayer from the set and frees its slot.
+    ///
+    /// The slot is reassigned once the proposals created until now have been removed, so the
+    /// votes of the relayer are never attributed to another relayer.
+    /// The bond of the relayer starts unbonding. Fails if the threshold would exceed the number
+    /// of remaining relayers, unless the last relayer is removed.
     pub fn unregister_relayer(relayer: T::AccountId) -> DispatchResult {
-        ensure!(Self::is_relayer(&relayer), Error::<T>::RelayerInvalid);
+        let slot = Self::relayer_slot(&relayer).ok_or(Error::<T>::RelayerInvalid)?;
+        let remaining = Self::relayer_count().saturating_sub(1);
+        ensure!(
+            remaining == 0 || Self::vote_threshold().votes(remaining) <= remaining,
+            Error::<T>::ThresholdExceedsRelayers
+        );
         <Relayers<T>>::remove(&relayer);
+        <RelayerKeys<T>>::remove(&relayer);
+        let now = <frame_system::Pallet<T>>::block_number();
+        <Bonds<T>>::mutate(&relayer, |bond| {
+            if let Some(bond) = bond {
+                bond.until = Some(now);
+            }
+        });
+        <OccupiedSlots>::mutate(|slots| slots.remove(slot));
+        <FreedSlots<T>>::insert(slot, now);
         <RelayerCount>::mutate(|i| *i -= 1);
         Self::deposit_event(RawEvent::RelayerRemoved(relayer));
         Ok(())
@@ -437,70 +1743,221 @@ impl<T: Config> Module<T> {
         who: T::AccountId,
         nonce: DepositNonce,
         src_id: ChainId,
-        prop: Box<T::Proposal>,
+        content: &ProposalContent<T::Proposal>,
         in_favour: bool,
     ) -> DispatchResult {
         let now = <frame_system::Pallet<T>>::block_number();
-        let mut votes = match <Votes<T>>::get(src_id, (nonce, prop.clone())) {
-            Some(v) => v,
+        let prop_hash = Self::content_hash(content);
+        let slot = Self::relayer_slot(&who).ok_or(Error::<T>::MustBeRelayer)?;
+        let (mut votes, is_new) = Self::votes_or_new(src_id, nonce, prop_hash, now);
+        Self::ensure_can_vote(slot, src_id, nonce, &votes, now)?;
+
+        if is_new {
+            if let ProposalContent::Call(call) = content {
+                // Proposals completed before V2 are only recorded in the legacy map
+                ensure!(
+                    !Self::legacy_votes_remain()
+                        || !<Votes<T>>::contains_key(src_id, (nonce, (**call).clone())),
+                    Error::<T>::ProposalAlreadyComplete
+                );
+                Self::note_proposal_call(prop_hash, (**call).clone());
+            }
+            let prune_at = votes.expiry.saturating_add(T::ProposalRetention::get());
+            <PruneQueue<T>>::append(prune_at, (src_id, nonce, prop_hash));
+        }
+
+        if in_favour {
+            votes.votes_for.insert(slot);
+            Self::deposit_event(RawEvent::VoteFor(src_id, nonce, who));
+        } else {
+            votes.votes_against.insert(slot);
+            Self::deposit_event(RawEvent::VoteAgainst(src_id, nonce, who));
+        }
+
+        <Proposals<T>>::insert(src_id, (nonce, prop_hash), votes);
+
+        Ok(())
+    }
+
+    /// Returns the votes of a proposal, or the votes of a new proposal created at `now`.
+    /// The flag is true if the proposal doesn't exist yet.
+    fn votes_or_new(
+        src_id: ChainId,
+        nonce: DepositNonce,
+        prop_hash: T::Hash,
+        now: T::BlockNumber,
+    ) -> (ProposalVotes<T::BlockNumber>, bool) {
+        match <Proposals<T>>::get(src_id, (nonce, prop_hash)) {
+            Some(v) => (v, false),
             None => {
                 let mut v = ProposalVotes::default();
                 v.expiry = now + T::ProposalLifetime::get();
-                v
+                (v, true)
             }
-        };
+        }
+    }
 
-        // Ensure the proposal isn't complete and relayer hasn't already voted
+    /// Ensures the proposal isn't complete or expired and the relayer in `slot` hasn't already
+    /// voted
+    fn ensure_can_vote(
+        slot: u32,
+        src_id: ChainId,
+        nonce: DepositNonce,
+        votes: &ProposalVotes<T::BlockNumber>,
+        now: T::BlockNumber,
+    ) -> DispatchResult {
         ensure!(!votes.is_complete(), Error::<T>::ProposalAlreadyComplete);
+        ensure!(
+            !<ExecutedNonces<T>>::contains_key(src_id, nonce),
+            Error::<T>::NonceAlreadyExecuted
+        );
         ensure!(!votes.is_expired(now), Error::<T>::ProposalExpired);
-        ensure!(!votes.has_voted(&who), Error::<T>::RelayerAlreadyVoted);
+        Self::ensure_not_removed(src_id, votes)?;
+        ensure!(!votes.has_voted(slot), Error::<T>::RelayerAlreadyVoted);
+        Ok(())
+    }
 
-        if in_favour {
-            votes.votes_for.push(who.clone());
-            Self::deposit_event(RawEvent::VoteFor(src_id, nonce, who.clone()));
-        } else {
-            votes.votes_against.push(who.clone());
-            Self::deposit_event(RawEvent::VoteAgainst(src_id, nonce, who.clone()));
-        }
+    /// Ensures the proposal was created after its source chain was last removed
+    fn ensure_not_removed(
+        src_id: ChainId,
+        votes: &ProposalVotes<T::BlockNumber>,
+    ) -> DispatchResult {
+        let created = votes.expiry.saturating_sub(T::ProposalLifetime::get());
+        ensure!(
+            Self::chain_removed_at(src_id).map_or(true, |removed| removed < created),
+            Error::<T>::ProposalFromRemovedChain
+        );
+        Ok(())
+    }
 
-        <Votes<T>>::insert(src_id, (nonce, prop.clone()), votes.clone());
+    /// Stores the call of a new proposal, or adds a reference if it's already known.
+    fn note_proposal_call(prop_hash: T::Hash, prop: T::Proposal) {
+        <ProposalCalls<T>>::mutate(prop_hash, |entry| match entry {
+            Some((_, refs)) => *refs += 1,
+            None => *entry = Some((prop, 1)),
+        });
+    }
 
-        Ok(())
+    /// Removes a reference to a proposal call, deleting the call once it's no longer referenced.
+    fn release_proposal_call(prop_hash: T::Hash) {
+        <ProposalCalls<T>>::mutate_exists(prop_hash, |entry| {
+            if let Some((_, refs)) = entry {
+                *refs = refs.saturating_sub(1);
+                if *refs == 0 {
+                    *entry = None;
+                }
+            }
+        });
+    }
+
+    /// Returns true if the retention period of the proposal has passed.
+    /// By then the proposal is either complete or expired.
+    fn is_prunable(votes: &ProposalVotes<T::BlockNumber>, now: T::BlockNumber) -> bool {
+        votes.expiry.saturating_add(T::ProposalRetention::get()) <= now
+    }
+
+    /// Removes a proposal and releases its call.
+    /// Signals `ProposalExpired` if the proposal was never completed.
+    fn remove_proposal(
+        src_id: ChainId,
+        nonce: DepositNonce,
+        prop_hash: T::Hash,
+        votes: &ProposalVotes<T::BlockNumber>,
+    ) {
+        <Proposals<T>>::remove(src_id, (nonce, prop_hash));
+        Self::release_proposal_call(prop_hash);
+        match votes.status {
+            ProposalStatus::Initiated => {
+                Self::deposit_event(RawEvent::ProposalExpired(src_id, nonce))
+            }
+            ProposalStatus::Executed | ProposalStatus::Failed => {
+                <ProposalRetries>::remove(src_id, nonce)
+            }
+            _ => (),
+        }
+    }
+
+    /// Removes the proposals scheduled for removal at `now`.
+    /// Returns the number of scheduled entries processed.
+    fn prune_scheduled_proposals(now: T::BlockNumber) -> u32 {
+        let scheduled = <PruneQueue<T>>::take(now);
+        let count = scheduled.len() as u32;
+        for (src_id, nonce, prop_hash) in scheduled {
+            if let Some(votes) = <Proposals<T>>::get(src_id, (nonce, prop_hash)) {
+                // The proposal may have been removed and created again since it was scheduled
+                if Self::is_prunable(&votes, now) {
+                    Self::remove_proposal(src_id, nonce, prop_hash, &votes);
+                }
+            }
+        }
+        count
     }
 
     /// Attempts to finalize or cancel the proposal if the vote count allows.
+    /// Returns the weight consumed by the proposal if it was executed.
     fn try_resolve_proposal(
         nonce: DepositNonce,
         src_id: ChainId,
-        prop: Box<T::Proposal>,
-    ) -> DispatchResult {
-        if let Some(mut votes) = <Votes<T>>::get(src_id, (nonce, prop.clone())) {
+        content: ProposalContent<T::Proposal>,
+    ) -> Result<Option<Weight>, DispatchError> {
+        let prop_hash = Self::content_hash(&content);
+        if let Some(mut votes) = <Proposals<T>>::get(src_id, (nonce, prop_hash)) {
             let now = <frame_system::Pallet<T>>::block_number();
             ensure!(!votes.is_complete(), Error::<T>::ProposalAlreadyComplete);
             ensure!(!votes.is_expired(now), Error::<T>::ProposalExpired);
-
-            let status = votes.try_to_complete(<RelayerThreshold>::get(), <RelayerCount>::get());
-            <Votes<T>>::insert(src_id, (nonce, prop.clone()), votes.clone());
-
-            match status {
-                ProposalStatus::Approved => Self::finalize_execution(src_id, nonce, prop),
-                ProposalStatus::Rejected => Self::cancel_execution(src_id, nonce),
-                _ => Ok(()),
-            }
+            Self::ensure_not_removed(src_id, &votes)?;
+            // Only one proposal may be executed for each deposit
+            ensure!(
+                !<ExecutedNonces<T>>::contains_key(src_id, nonce),
+                Error::<T>::NonceAlreadyExecuted
+            );
+
+            let status = votes.try_to_complete(Self::vote_threshold(), <RelayerCount>::get());
+            let executed = match status {
+                ProposalStatus::Approved => {
+                    <ExecutedNonces<T>>::insert(src_id, nonce, prop_hash);
+                    Self::deposit_event(RawEvent::ProposalApproved(src_id, nonce));
+                    let (outcome, weight) = Self::finalize(src_id, nonce, content);
+                    if outcome == ProposalStatus::Executed {
+                        Self::reward_voters(&votes);
+                    }
+                    votes.status = outcome;
+                    Some(weight)
+                }
+                ProposalStatus::Rejected => {
+                    Self::cancel_execution(src_id, nonce)?;
+                    None
+                }
+                _ => None,
+            };
+            <Proposals<T>>::insert(src_id, (nonce, prop_hash), votes);
+
+            Ok(executed)
         } else {
             Err(Error::<T>::ProposalDoesNotExist)?
         }
     }
 
+    /// Commits a vote and executes or cancels the proposal if the vote count allows.
+    fn vote(
+        who: T::AccountId,
+        nonce: DepositNonce,
+        src_id: ChainId,
+        content: ProposalContent<T::Proposal>,
+        in_favour: bool,
+    ) -> Result<Option<Weight>, DispatchError> {
+        Self::commit_vote(who, nonce, src_id, &content, in_favour)?;
+        Self::try_resolve_proposal(nonce, src_id, content)
+    }
+
     /// Commits a vote in favour of the proposal and executes it if the vote threshold is met.
     fn vote_for(
         who: T::AccountId,
         nonce: DepositNonce,
         src_id: ChainId,
         prop: Box<T::Proposal>,
-    ) -> DispatchResult {
-        Self::commit_vote(who, nonce, src_id, prop.clone(), true)?;
-        Self::try_resolve_proposal(nonce, src_id, prop)
+    ) -> Result<Option<Weight>, DispatchError> {
+        Self::vote(who, nonce, src_id, ProposalContent::Call(prop), true)
     }
 
     /// Commits a vote against the proposal and cancels it if more than (relayers.len() - threshold)
@@ -510,23 +1967,263 @@ impl<T: Config> Module<T> {
         nonce: DepositNonce,
         src_id: ChainId,
         prop: Box<T::Proposal>,
-    ) -> DispatchResult {
-        Self::commit_vote(who, nonce, src_id, prop.clone(), false)?;
-        Self::try_resolve_proposal(nonce, src_id, prop)
+    ) -> Result<Option<Weight>, DispatchError> {
+        Self::vote(who, nonce, src_id, ProposalContent::Call(prop), false)
+    }
+
+    /// Validates a proposal and commits a vote in favour of it.
+    /// Returns the weight consumed by the proposal if it was executed.
+    fn acknowledge(
+        who: T::AccountId,
+        nonce: DepositNonce,
+        src_id: ChainId,
+        r_id: ResourceId,
+        call: Box<T::Proposal>,
+    ) -> Result<Option<Weight>, DispatchError> {
+        Self::ensure_inbound(src_id)?;
+        let info = Self::ensure_resource(r_id, src_id)?;
+        ensure!(
+            T::ProposalValidator::validate(r_id, &info, &call),
+            Error::<T>::InvalidProposal
+        );
+        Self::vote_for(who, nonce, src_id, call)
     }
 
-    /// Execute the proposal and signals the result as an event
+    /// Validates a proposal and commits a vote against it.
+    fn reject(
+        who: T::AccountId,
+        nonce: DepositNonce,
+        src_id: ChainId,
+        r_id: ResourceId,
+        call: Box<T::Proposal>,
+    ) -> Result<Option<Weight>, DispatchError> {
+        Self::ensure_inbound(src_id)?;
+        Self::ensure_resource(r_id, src_id)?;
+        Self::vote_against(who, nonce, src_id, call)
+    }
+
+    /// Commits a vote in favour of a proposal for each relayer that signed it, then executes the
+    /// proposal. All signatures are checked before any vote is committed.
+    fn vote_signed(
+        nonce: DepositNonce,
+        src_id: ChainId,
+        r_id: ResourceId,
+        call: Box<T::Proposal>,
+        signatures: &[(T::AccountId, MultiSignature)],
+    ) -> Result<Option<Weight>, DispatchError> {
+        let now = <frame_system::Pallet<T>>::block_number();
+        let prop_hash = Self::proposal_hash(&call);
+        let payload = Self::signed_proposal_payload(src_id, nonce, r_id, prop_hash);
+        let (votes, _) = Self::votes_or_new(src_id, nonce, prop_hash, now);
+
+        let mut signers = Bitfield::default();
+        for (relayer, signature) in signatures {
+            let slot = Self::relayer_slot(relayer).ok_or(Error::<T>::MustBeRelayer)?;
+            ensure!(!signers.contains(slot), Error::<T>::DuplicateSignature);
+            let key = Self::relayer_key(relayer).ok_or(Error::<T>::RelayerKeyNotSet)?;
+            ensure!(
+                verify_signature(signature, &key, &payload),
+                Error::<T>::InvalidSignature
+            );
+            Self::ensure_can_vote(slot, src_id, nonce, &votes, now)?;
+            signers.insert(slot);
+        }
+        ensure!(
+            votes.votes_for.count() + signers.count() >= Self::required_votes(),
+            Error::<T>::InsufficientSignatures
+        );
+
+        let content = ProposalContent::Call(call);
+        for (relayer, _) in signatures {
+            Self::commit_vote(relayer.clone(), nonce, src_id, &content, true)?;
+        }
+        Self::try_resolve_proposal(nonce, src_id, content)
+    }
+
+    /// Commits each vote of a batch and signals its result as an event. `base` is the weight of
+    /// the batch, excluding the executed proposals.
+    ///
+    /// Each vote runs in its own storage transaction, so a failed vote leaves the others intact.
+    fn vote_batch(
+        proposals: Vec<(DepositNonce, ChainId, ResourceId, T::Proposal)>,
+        base: Weight,
+        vote: impl Fn(
+            DepositNonce,
+            ChainId,
+            ResourceId,
+            Box<T::Proposal>,
+        ) -> Result<Option<Weight>, DispatchError>,
+    ) -> PostDispatchInfo {
+        // An empty batch is not free
+        let mut pays_fee = if proposals.is_empty() {
+            Pays::Yes
+        } else {
+            Pays::No
+        };
+        let mut weight = base;
+        for (nonce, src_id, r_id, call) in proposals {
+            match Self::rollback_on_error(|| vote(nonce, src_id, r_id, Box::new(call))) {
+                Ok(executed) => {
+                    weight = weight.saturating_add(executed.unwrap_or_default());
+                    Self::deposit_event(RawEvent::BatchVoteSucceeded(src_id, nonce));
+                }
+                Err(e) => {
+                    pays_fee = Pays::Yes;
+                    Self::deposit_event(RawEvent::BatchVoteFailed(src_id, nonce, e));
+                }
+            }
+        }
+        PostDispatchInfo {
+            actual_weight: Some(weight),
+            pays_fee,
+        }
+    }
+
+    /// Executes a call or deposit proposal.
+    /// Returns the resulting status of the proposal and the weight consumed.
+    fn finalize(
+        src_id: ChainId,
+        nonce: DepositNonce,
+        content: ProposalContent<T::Proposal>,
+    ) -> (ProposalStatus, Weight) {
+        match content {
+            ProposalContent::Call(call) => Self::finalize_execution(src_id, nonce, call),
+            ProposalContent::Deposit(r_id, data) => {
+                Self::finalize_deposit(src_id, nonce, r_id, data)
+            }
+        }
+    }
+
+    /// Execute the proposal and signals the result as an event.
+    /// Returns the resulting status of the proposal and the actual weight consumed by the call.
     fn finalize_execution(
         src_id: ChainId,
         nonce: DepositNonce,
         call: Box<T::Proposal>,
-    ) -> DispatchResult {
-        Self::deposit_event(RawEvent::ProposalApproved(src_id, nonce));
-        call.dispatch(frame_system::RawOrigin::Signed(Self::account_id()).into())
-            .map(|_| ())
-            .map_err(|e| e.error)?;
-        Self::deposit_event(RawEvent::ProposalSucceeded(src_id, nonce));
-        Ok(())
+    ) -> (ProposalStatus, Weight) {
+        let info = call.get_dispatch_info();
+        let result = Self::rollback_on_error(|| {
+            call.dispatch(frame_system::RawOrigin::Signed(Self::account_id()).into())
+        });
+        let weight = extract_actual_weight(&result, &info);
+        match result {
+            Ok(_) => {
+                Self::deposit_event(RawEvent::ProposalSucceeded(src_id, nonce));
+                (ProposalStatus::Executed, weight)
+            }
+            Err(e) => {
+                Self::deposit_event(RawEvent::ProposalFailed(src_id, nonce, e.error));
+                (ProposalStatus::Failed, weight)
+            }
+        }
+    }
+
+    /// Executes a deposit through the resource handler and signals the result as an event.
+    /// Returns the resulting status of the proposal and the weight of the handler.
+    fn finalize_deposit(
+        src_id: ChainId,
+        nonce: DepositNonce,
+        r_id: ResourceId,
+        data: Vec<u8>,
+    ) -> (ProposalStatus, Weight) {
+        let weight = T::ResourceHandler::weight(r_id, &data);
+        let result: DispatchResult = Self::resources(r_id)
+            .ok_or(Error::<T>::ResourceDoesNotExist.into())
+            .and_then(|info| {
+                let deposit =
+                    Deposit::parse(info.kind, &data).ok_or(Error::<T>::InvalidDepositData)?;
+                Self::rollback_on_error(|| T::ResourceHandler::handle(r_id, &info, deposit))
+            });
+        match result {
+            Ok(()) => {
+                Self::deposit_event(RawEvent::ProposalSucceeded(src_id, nonce));
+                (ProposalStatus::Executed, weight)
+            }
+            Err(e) => {
+                Self::deposit_event(RawEvent::ProposalFailed(src_id, nonce, e));
+                (ProposalStatus::Failed, weight)
+            }
+        }
+    }
+
+    /// Runs `f` in a storage transaction, discarding the changes it made if it fails.
+    fn rollback_on_error<R, E>(f: impl FnOnce() -> Result<R, E>) -> Result<R, E> {
+        with_transaction(|| {
+            let result = f();
+            if result.is_ok() {
+                TransactionOutcome::Commit(result)
+            } else {
+                TransactionOutcome::Rollback(result)
+            }
+        })
+    }
+
+    /// Awards a reward point to the relayers that voted for an executed proposal and issues the
+    /// `ProposalReward` into the reward pot.
+    ///
+    /// Every relayer whose vote in favour is present at execution is credited. Slots are not
+    /// reassigned while proposals with their votes exist, so each vote belongs to the current
+    /// holder of its slot.
+    fn reward_voters(votes: &ProposalVotes<T::BlockNumber>) {
+        let voters: Vec<T::AccountId> = <Relayers<T>>::iter()
+            .filter(|(_, slot)| votes.votes_for.contains(*slot))
+            .map(|(relayer, _)| relayer)
+            .collect();
+
+        let awarded = voters.len() as u32;
+        for relayer in voters {
+            <RewardPoints<T>>::mutate(&relayer, |points| *points = points.saturating_add(1));
+        }
+        <TotalRewardPoints>::mutate(|total| *total = total.saturating_add(awarded));
+
+        let reward = T::ProposalReward::get();
+        if !reward.is_zero() {
+            drop(T::Currency::deposit_creating(
+                &Self::rewards_account_id(),
+                reward,
+            ));
+        }
+    }
+
+    /// Executes a failed proposal again, if it hasn't reached the retry limit.
+    /// Returns the actual weight consumed by the proposal.
+    fn retry_execution(
+        nonce: DepositNonce,
+        src_id: ChainId,
+        content: ProposalContent<T::Proposal>,
+    ) -> Result<Weight, DispatchError> {
+        let prop_hash = Self::content_hash(&content);
+        let mut votes = <Proposals<T>>::get(src_id, (nonce, prop_hash))
+            .ok_or(Error::<T>::ProposalDoesNotExist)?;
+        ensure!(
+            votes.status == ProposalStatus::Failed,
+            Error::<T>::ProposalNotFailed
+        );
+        Self::ensure_not_removed(src_id, &votes)?;
+        let retries = <ProposalRetries>::get(src_id, nonce);
+        ensure!(
+            retries < T::MaxProposalRetries::get(),
+            Error::<T>::RetryLimitReached
+        );
+
+        <ProposalRetries>::insert(src_id, nonce, retries + 1);
+        let (outcome, weight) = Self::finalize(src_id, nonce, content);
+        if outcome == ProposalStatus::Executed {
+            Self::reward_voters(&votes);
+        }
+        votes.status = outcome;
+        <Proposals<T>>::insert(src_id, (nonce, prop_hash), votes);
+
+        Ok(weight)
+    }
+
+    /// Builds the post dispatch info for a voting call. The weight of the proposal is only
+    /// included if it was executed.
+    fn vote_post_info(base: Weight, executed: Option<Weight>, pays_fee: Pays) -> PostDispatchInfo {
+        PostDispatchInfo {
+            actual_weight: Some(base.saturating_add(executed.unwrap_or_default())),
+            pays_fee,
+        }
     }
 
     /// Cancels a proposal.
@@ -536,40 +2233,60 @@ impl<T: Config> Module<T> {
     }
 
     /// Initiates a transfer of a fungible asset out of the chain. This should be called by another pallet.
+    /// The depositor is charged the fee set for the resource and destination chain.
     pub fn transfer_fungible(
+        depositor: T::AccountId,
         dest_id: ChainId,
         resource_id: ResourceId,
         to: Vec<u8>,
         amount: U256,
     ) -> DispatchResult {
-        ensure!(
-            Self::chain_whitelisted(dest_id),
-            Error::<T>::ChainNotWhitelisted
-        );
+        Self::ensure_outbound(dest_id)?;
+        Self::ensure_resource(resource_id, dest_id)?;
+        let fee = Self::collect_fee(&depositor, dest_id, resource_id)?;
         let nonce = Self::bump_nonce(dest_id);
+        let deposit = Deposit::Fungible {
+            amount,
+            recipient: to.clone(),
+        };
+        let payload_hash =
+            Self::record_deposit(dest_id, nonce, resource_id, &deposit, depositor.clone());
         Self::deposit_event(RawEvent::FungibleTransfer(
             dest_id,
             nonce,
             resource_id,
             amount,
             to,
+            depositor,
+            payload_hash,
+            fee,
         ));
         Ok(())
     }
 
     /// Initiates a transfer of a nonfungible asset out of the chain. This should be called by another pallet.
+    /// The depositor is charged the fee set for the resource and destination chain.
+    /// The token ID is a big endian integer of at most 32 bytes.
     pub fn transfer_nonfungible(
+        depositor: T::AccountId,
         dest_id: ChainId,
         resource_id: ResourceId,
         token_id: Vec<u8>,
         to: Vec<u8>,
         metadata: Vec<u8>,
     ) -> DispatchResult {
-        ensure!(
-            Self::chain_whitelisted(dest_id),
-            Error::<T>::ChainNotWhitelisted
-        );
+        Self::ensure_outbound(dest_id)?;
+        Self::ensure_resource(resource_id, dest_id)?;
+        ensure!(token_id.len() <= 32, Error::<T>::InvalidTokenId);
+        let fee = Self::collect_fee(&depositor, dest_id, resource_id)?;
         let nonce = Self::bump_nonce(dest_id);
+        let deposit = Deposit::NonFungible {
+            token_id: U256::from_big_endian(&token_id),
+            recipient: to.clone(),
+            metadata: metadata.clone(),
+        };
+        let payload_hash =
+            Self::record_deposit(dest_id, nonce, resource_id, &deposit, depositor.clone());
         Self::deposit_event(RawEvent::NonFungibleTransfer(
             dest_id,
             nonce,
@@ -577,29 +2294,96 @@ impl<T: Config> Module<T> {
             token_id,
             to,
             metadata,
+            depositor,
+            payload_hash,
+            fee,
         ));
         Ok(())
     }
 
     /// Initiates a transfer of generic data out of the chain. This should be called by another pallet.
+    /// The depositor is charged the fee set for the resource and destination chain.
     pub fn transfer_generic(
+        depositor: T::AccountId,
         dest_id: ChainId,
         resource_id: ResourceId,
         metadata: Vec<u8>,
     ) -> DispatchResult {
-        ensure!(
-            Self::chain_whitelisted(dest_id),
-            Error::<T>::ChainNotWhitelisted
-        );
+        Self::ensure_outbound(dest_id)?;
+        Self::ensure_resource(resource_id, dest_id)?;
+        let fee = Self::collect_fee(&depositor, dest_id, resource_id)?;
         let nonce = Self::bump_nonce(dest_id);
+        let deposit = Deposit::Generic {
+            metadata: metadata.clone(),
+        };
+        let payload_hash =
+            Self::record_deposit(dest_id, nonce, resource_id, &deposit, depositor.clone());
         Self::deposit_event(RawEvent::GenericTransfer(
             dest_id,
             nonce,
             resource_id,
             metadata,
+            depositor,
+            payload_hash,
+            fee,
         ));
         Ok(())
     }
+
+    /// Charges the fee for a transfer to the depositor, paying it into the fee account.
+    /// The depositor's account is kept alive. Returns the fee.
+    fn collect_fee(
+        depositor: &T::AccountId,
+        dest_id: ChainId,
+        resource_id: ResourceId,
+    ) -> Result<FeeBalanceOf<T>, DispatchError> {
+        let fee = Self::bridge_fee((dest_id, resource_id));
+        T::FeeCurrency::transfer(
+            depositor,
+            &Self::fees_account_id(),
+            fee,
+            ExistenceRequirement::KeepAlive,
+        )?;
+        Ok(fee)
+    }
+
+    /// Stores the record of a transfer and schedules its removal.
+    /// Returns the `deposit_hash` of the recorded payload, which relayers vote on at the
+    /// destination chain.
+    fn record_deposit(
+        dest_id: ChainId,
+        nonce: DepositNonce,
+        resource_id: ResourceId,
+        deposit: &Deposit,
+        depositor: T::AccountId,
+    ) -> T::Hash {
+        let now = <frame_system::Pallet<T>>::block_number();
+        let payload = deposit.to_data();
+        let payload_hash = Self::deposit_hash(resource_id, &payload);
+        let record = DepositRecord {
+            resource_id,
+            payload,
+            depositor,
+            block: now,
+        };
+        <DepositRecords<T>>::insert(dest_id, nonce, record);
+
+        let retention = T::DepositRecordRetention::get();
+        if !retention.is_zero() {
+            <DepositPruneQueue<T>>::append(now.saturating_add(retention), (dest_id, nonce));
+        }
+        payload_hash
+    }
+
+    /// Removes the deposit records scheduled for removal at `now`.
+    /// Returns the number of records removed.
+    fn prune_deposit_records(now: T::BlockNumber) -> u32 {
+        let scheduled = <DepositPruneQueue<T>>::take(now);
+        for (dest_id, nonce) in &scheduled {
+            <DepositRecords<T>>::remove(dest_id, nonce);
+        }
+        scheduled.len() as u32
+    }
 }
 
 /// Simple ensure origin for the bridge account
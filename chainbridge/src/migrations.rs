@@ -0,0 +1,219 @@
+//! Storage migrations for chainbridge
+
+use super::*;
+use frame_support::storage::{
+    migration::{put_storage_value, StorageIterator},
+    StoragePrefixedMap,
+};
+use frame_support::{Blake2_128Concat, StorageHasher};
+use sp_core::hashing::blake2_256;
+use sp_std::collections::btree_map::BTreeMap;
+
+/// Removes the pending proposals of the `Votes` map, which used the full encoded proposal as
+/// part of its keys.
+///
+/// Proposals are now stored in `Proposals` under the hash of their call. The keys of `Votes`
+/// are opaque hashes, so existing proposals cannot be re-keyed. Pending proposals are dropped
+/// and have to be voted on again by the relayers, their number is signalled by
+/// `LegacyProposalsDropped`. Completed proposals are kept, as they are the only record of the
+/// deposits already executed, and new proposals for them are refused until they are removed
+/// by `purge_legacy_votes`.
+pub fn migrate_to_v2<T: Config>() -> Weight {
+    if StorageVersion::get() != Releases::V1 {
+        return 0;
+    }
+
+    let mut dropped = 0u32;
+    let mut kept = 0u32;
+    <Votes<T>>::translate_values::<deprecated::ProposalVotes<T::AccountId, T::BlockNumber>, _>(
+        |old| match old.status {
+            ProposalStatus::Initiated => {
+                dropped += 1;
+                None
+            }
+            _ => {
+                kept += 1;
+                Some(old)
+            }
+        },
+    );
+    LegacyVotesRemain::put(kept > 0);
+    if dropped > 0 {
+        Module::<T>::deposit_event(RawEvent::LegacyProposalsDropped(dropped));
+    }
+    StorageVersion::put(Releases::V2);
+
+    T::BlockWeights::get().max_block
+}
+
+/// Removes up to `limit` entries of the legacy `Votes` map, returning the number removed.
+/// New proposals are no longer checked against the map once it is empty.
+pub fn purge_legacy_votes<T: Config>(limit: u32) -> u32 {
+    type OldVotes<T> = deprecated::ProposalVotes<
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    let removed = StorageIterator::<OldVotes<T>>::new(b"ChainBridge", b"Votes")
+        .drain()
+        .take(limit as usize)
+        .count() as u32;
+    if StorageIterator::<OldVotes<T>>::new(b"ChainBridge", b"Votes")
+        .next()
+        .is_none()
+    {
+        LegacyVotesRemain::kill();
+    }
+    removed
+}
+
+/// Converts the method names stored in `Resources` into a `ResourceInfo`.
+///
+/// The kind of a resource can't be derived from its method name, so existing resources are
+/// registered as enabled generic resources that may be bridged with any chain. Admins should
+/// update them with `set_resource` afterwards.
+pub fn migrate_to_v3<T: Config>() -> Weight {
+    if StorageVersion::get() != Releases::V2 {
+        return 0;
+    }
+
+    let resources: Vec<(Vec<u8>, Vec<u8>)> =
+        StorageIterator::<Vec<u8>>::new(b"ChainBridge", b"Resources").collect();
+    for (key, method) in resources {
+        let info = ResourceInfo::new(ResourceKind::Generic, method);
+        put_storage_value(b"ChainBridge", b"Resources", &key, info);
+    }
+    StorageVersion::put(Releases::V3);
+
+    T::BlockWeights::get().max_block
+}
+
+/// Moves `Relayers`, `ChainNonces` and `Resources` from `opaque_blake2_256` to
+/// `blake2_128_concat` keys, so they can be enumerated.
+///
+/// Opaque keys can't be reversed, so the relayers and resource IDs are taken from
+/// `Config::LegacyRelayers` and `Config::LegacyResourceIds`. All chain IDs are checked.
+/// Entries whose key isn't known are removed and the relayer count is set to the number of
+/// relayers kept.
+pub fn migrate_to_v4<T: Config>() -> Weight {
+    if StorageVersion::get() != Releases::V3 {
+        return 0;
+    }
+
+    let mut old_relayers = take_opaque_map::<bool>(b"Relayers");
+    let mut kept = 0u32;
+    for relayer in T::LegacyRelayers::get() {
+        if let Some(true) = old_relayers.remove(&blake2_256(&relayer.encode())[..]) {
+            // Relayers are assigned their slots by `migrate_to_v5`
+            let key = Blake2_128Concat::hash(&relayer.encode());
+            put_storage_value(b"ChainBridge", b"Relayers", &key, true);
+            kept += 1;
+        }
+    }
+    RelayerCount::put(kept);
+
+    let mut old_chains = take_opaque_map::<DepositNonce>(b"ChainNonces");
+    for id in ChainId::min_value()..=ChainId::max_value() {
+        if let Some(nonce) = old_chains.remove(&blake2_256(&id.encode())[..]) {
+            ChainNonces::insert(id, nonce);
+        }
+    }
+
+    let mut old_resources = take_opaque_map::<ResourceInfo>(b"Resources");
+    for id in T::LegacyResourceIds::get() {
+        if let Some(info) = old_resources.remove(&blake2_256(&id.encode())[..]) {
+            Resources::insert(id, info);
+        }
+    }
+
+    if !old_relayers.is_empty() || !old_resources.is_empty() {
+        log::warn!(
+            target: "runtime::chainbridge",
+            "Removed {} relayers and {} resources missing from the legacy lists",
+            old_relayers.len(),
+            old_resources.len()
+        );
+    }
+
+    StorageVersion::put(Releases::V4);
+
+    T::BlockWeights::get().max_block
+}
+
+/// Assigns a slot to each relayer and converts the votes of proposals into bitfields of
+/// relayer slots.
+///
+/// Votes of accounts that are no longer relayers are dropped.
+pub fn migrate_to_v5<T: Config>() -> Weight {
+    match StorageVersion::get() {
+        Releases::V4 => (),
+        Releases::V5 => return 0,
+        version => {
+            log::error!(
+                target: "runtime::chainbridge",
+                "Storage at {:?} can't be migrated to V5",
+                version
+            );
+            return 0;
+        }
+    }
+
+    let mut slots = Bitfield::default();
+    <Relayers<T>>::translate::<bool, _>(|_, _| {
+        let slot = slots.first_free();
+        slots.insert(slot);
+        Some(slot)
+    });
+    OccupiedSlots::put(slots);
+
+    let to_slots = |voters: Vec<T::AccountId>| -> Bitfield {
+        voters
+            .iter()
+            .filter_map(|who| <Relayers<T>>::get(who))
+            .collect()
+    };
+    <Proposals<T>>::translate_values::<deprecated::ProposalVotes<T::AccountId, T::BlockNumber>, _>(
+        |old| {
+            Some(ProposalVotes {
+                votes_for: to_slots(old.votes_for),
+                votes_against: to_slots(old.votes_against),
+                status: old.status,
+                expiry: old.expiry,
+            })
+        },
+    );
+
+    StorageVersion::put(Releases::V5);
+
+    T::BlockWeights::get().max_block
+}
+
+/// Checks the storage has been migrated to the current version, e.g. from the `post_upgrade`
+/// hook of a try-runtime test.
+pub fn check_version<T: Config>() -> Result<(), &'static str> {
+    match StorageVersion::get() {
+        Releases::V5 => Ok(()),
+        _ => Err("ChainBridge storage is not at the current version"),
+    }
+}
+
+/// Storage formats replaced by migrations
+pub mod deprecated {
+    use super::*;
+
+    /// Votes on a proposal by relayer account, used by `Votes` and replaced in `Proposals` in V5
+    #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+    pub struct ProposalVotes<AccountId, BlockNumber> {
+        pub votes_for: Vec<AccountId>,
+        pub votes_against: Vec<AccountId>,
+        pub status: ProposalStatus,
+        pub expiry: BlockNumber,
+    }
+}
+
+/// Removes all entries of an `opaque_blake2_256` map, returning them by their hashed key
+fn take_opaque_map<V: Decode>(item: &[u8]) -> BTreeMap<Vec<u8>, V> {
+    StorageIterator::<V>::new(b"ChainBridge", item)
+        .drain()
+        .collect()
+}
@@ -2,14 +2,21 @@
 
 use super::*;
 
-use frame_support::{assert_ok, ord_parameter_types, parameter_types, weights::Weight};
+use frame_support::{
+    assert_ok, ord_parameter_types, parameter_types,
+    traits::{Currency, ExistenceRequirement, Get},
+    weights::Weight,
+};
 use frame_system::{self as system};
 use sp_core::H256;
+use sp_keystore::{testing::KeyStore, KeystoreExt};
 use sp_runtime::{
     testing::Header,
     traits::{AccountIdConversion, BlakeTwo256, IdentityLookup},
     Perbill,
 };
+use std::cell::RefCell;
+use std::sync::Arc;
 
 use crate::{self as bridge, Config};
 pub use pallet_balances as balances;
@@ -48,8 +55,20 @@ impl frame_system::Config for Test {
     type OnSetCode = ();
 }
 
-parameter_types! {
-    pub const ExistentialDeposit: u64 = 1;
+thread_local! {
+    static EXISTENTIAL_DEPOSIT: RefCell<u64> = RefCell::new(1);
+}
+
+/// Existential deposit of `Balances`, which tests may raise with `set_existential_deposit`
+pub struct ExistentialDeposit;
+impl Get<u64> for ExistentialDeposit {
+    fn get() -> u64 {
+        EXISTENTIAL_DEPOSIT.with(|v| *v.borrow())
+    }
+}
+
+pub fn set_existential_deposit(amount: u64) {
+    EXISTENTIAL_DEPOSIT.with(|v| *v.borrow_mut() = amount);
 }
 
 ord_parameter_types! {
@@ -69,14 +88,81 @@ impl pallet_balances::Config for Test {
 parameter_types! {
     pub const TestChainId: u8 = 5;
     pub const ProposalLifetime: u64 = 50;
+    pub const ProposalRetention: u64 = 10;
+    pub const MaxProposalRetries: u32 = 2;
+    pub const MaxRelayers: u32 = 5;
+    pub const DepositRecordRetention: u64 = 20;
+    pub const MaxBatchSize: u32 = 3;
+    pub const RelayerBond: u64 = 10;
+    pub const UnbondingDelay: u64 = 10;
+    pub const ProposalReward: u64 = 0;
+    pub LegacyRelayers: Vec<u64> = vec![RELAYER_A, RELAYER_B];
+    pub LegacyResourceIds: Vec<ResourceId> = vec![derive_resource_id(1, b"remark")];
 }
 
 impl Config for Test {
     type Event = Event;
     type AdminOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type PauseOrigin = frame_system::EnsureSignedBy<One, Self::AccountId>;
     type Proposal = Call;
     type ChainId = TestChainId;
+    type ProposalValidator = bridge::MatchHandler;
+    type ResourceHandler = TestHandler;
+    type MaxRelayers = MaxRelayers;
     type ProposalLifetime = ProposalLifetime;
+    type ProposalRetention = ProposalRetention;
+    type MaxProposalRetries = MaxProposalRetries;
+    type DepositRecordRetention = DepositRecordRetention;
+    type MaxBatchSize = MaxBatchSize;
+    type Currency = Balances;
+    type RelayerBond = RelayerBond;
+    type UnbondingDelay = UnbondingDelay;
+    type Slash = ();
+    type ProposalReward = ProposalReward;
+    type FeeCurrency = Balances;
+    type LegacyRelayers = LegacyRelayers;
+    type LegacyResourceIds = LegacyResourceIds;
+    type WeightInfo = ();
+}
+
+pub const HANDLER_WEIGHT: Weight = 1_000;
+
+/// Pays out fungible deposits from the bridge account and accepts generic deposits.
+/// Non-fungible deposits are minted by recording their recipient under the token ID, and fail
+/// after minting if they have no metadata.
+pub struct TestHandler;
+impl ResourceHandler for TestHandler {
+    fn weight(_: ResourceId, _: &[u8]) -> Weight {
+        HANDLER_WEIGHT
+    }
+
+    fn handle(_: ResourceId, _: &ResourceInfo, deposit: Deposit) -> DispatchResult {
+        match deposit {
+            Deposit::Fungible { amount, recipient } => {
+                let to = u64::decode(&mut &recipient[..])
+                    .map_err(|_| DispatchError::Other("Invalid recipient"))?;
+                <Balances as Currency<_>>::transfer(
+                    &Bridge::account_id(),
+                    &to,
+                    amount.low_u64(),
+                    ExistenceRequirement::AllowDeath,
+                )
+            }
+            Deposit::NonFungible {
+                token_id,
+                recipient,
+                metadata,
+            } => {
+                frame_support::storage::unhashed::put(&token_id.encode(), &recipient);
+                ensure!(
+                    !metadata.is_empty(),
+                    DispatchError::Other("Missing metadata")
+                );
+                Ok(())
+            }
+            Deposit::Generic { .. } => Ok(()),
+        }
+    }
 }
 
 pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
@@ -98,6 +184,7 @@ frame_support::construct_runtime!(
 pub const RELAYER_A: u64 = 0x2;
 pub const RELAYER_B: u64 = 0x3;
 pub const RELAYER_C: u64 = 0x4;
+pub const DEPOSITOR: u64 = 0x5;
 pub const ENDOWED_BALANCE: u64 = 100_000_000;
 pub const TEST_THRESHOLD: u32 = 2;
 
@@ -106,12 +193,32 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
     let mut t = frame_system::GenesisConfig::default()
         .build_storage::<Test>()
         .unwrap();
+    // Relayers are endowed with their bond
+    let relayers = vec![RELAYER_A, RELAYER_B, RELAYER_C]
+        .into_iter()
+        .chain(10..10 + MaxRelayers::get() as u64)
+        .map(|relayer| (relayer, RelayerBond::get()));
     pallet_balances::GenesisConfig::<Test> {
-        balances: vec![(bridge_id, ENDOWED_BALANCE)],
+        balances: vec![(bridge_id, ENDOWED_BALANCE)]
+            .into_iter()
+            .chain(relayers)
+            .collect(),
     }
     .assimilate_storage(&mut t)
     .unwrap();
     let mut ext = sp_io::TestExternalities::new(t);
+    // Lets benchmarks sign proposals with generated keys
+    ext.register_extension(KeystoreExt(Arc::new(KeyStore::new())));
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+pub fn new_test_ext_with_genesis(config: bridge::GenesisConfig<Test>) -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::default()
+        .build_storage::<Test>()
+        .unwrap();
+    config.assimilate_storage(&mut t).unwrap();
+    let mut ext = sp_io::TestExternalities::new(t);
     ext.execute_with(|| System::set_block_number(1));
     ext
 }
@@ -123,17 +230,21 @@ pub fn new_test_ext_initialized(
 ) -> sp_io::TestExternalities {
     let mut t = new_test_ext();
     t.execute_with(|| {
-        // Set and check threshold
-        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD));
-        assert_eq!(Bridge::relayer_threshold(), TEST_THRESHOLD);
         // Add relayers
         assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
         assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_B));
         assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_C));
+        // Set and check threshold
+        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD));
+        assert_eq!(Bridge::relayer_threshold(), TEST_THRESHOLD);
         // Whitelist chain
         assert_ok!(Bridge::whitelist_chain(Origin::root(), src_id));
         // Set and check resource ID mapped to some junk data
-        assert_ok!(Bridge::set_resource(Origin::root(), r_id, resource));
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            r_id,
+            ResourceInfo::new(ResourceKind::Generic, resource)
+        ));
         assert_eq!(Bridge::resource_exists(r_id), true);
     });
     t
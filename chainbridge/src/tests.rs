@@ -1,12 +1,25 @@
 #![cfg(test)]
 
 use super::mock::{
-    assert_events, new_test_ext, Balances, Bridge, Call, Event, Origin, ProposalLifetime, System,
-    Test, TestChainId, ENDOWED_BALANCE, RELAYER_A, RELAYER_B, RELAYER_C, TEST_THRESHOLD,
+    assert_events, balances, new_test_ext, Balances, Bridge, Call, DepositRecordRetention, Event,
+    ExistentialDeposit, MaxProposalRetries, MaxRelayers, One, Origin, ProposalLifetime,
+    ProposalRetention, RelayerBond, System, Test, TestChainId, UnbondingDelay, DEPOSITOR,
+    ENDOWED_BALANCE, RELAYER_A, RELAYER_B, RELAYER_C, TEST_THRESHOLD,
 };
 use super::*;
 use crate::mock::new_test_ext_initialized;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+    assert_noop, assert_ok,
+    dispatch::DispatchError,
+    traits::{Currency, OnInitialize},
+};
+use sp_core::{ecdsa, ed25519, sr25519, Pair, H256};
+use sp_runtime::MultiSignature;
+
+/// Hash of the payload of an outbound transfer
+fn payload_hash(r_id: ResourceId, deposit: Deposit) -> H256 {
+    Bridge::deposit_hash(r_id, &deposit.to_data())
+}
 
 #[test]
 fn derive_ids() {
@@ -23,70 +36,147 @@ fn derive_ids() {
     assert_eq!(r_id, expected);
 }
 
+#[test]
+fn bitfield_slots() {
+    let mut bitfield = Bitfield::default();
+    assert_eq!(bitfield.count(), 0);
+    assert_eq!(bitfield.first_free(), 0);
+
+    for slot in 0..9 {
+        bitfield.insert(slot);
+    }
+    bitfield.insert(20);
+    assert!(bitfield.contains(8));
+    assert!(!bitfield.contains(9));
+    assert!(!bitfield.contains(1000));
+    assert_eq!(bitfield.count(), 10);
+    assert_eq!(bitfield.first_free(), 9);
+    assert_eq!(bitfield.encode().len(), 4);
+
+    bitfield.remove(3);
+    bitfield.remove(20);
+    assert_eq!(bitfield.first_free(), 3);
+    assert_eq!(
+        bitfield.iter().collect::<Vec<_>>(),
+        vec![0, 1, 2, 4, 5, 6, 7, 8]
+    );
+    assert_eq!(bitfield, vec![0, 1, 2, 4, 5, 6, 7, 8].into_iter().collect());
+    // Trailing empty bytes are dropped
+    assert_eq!(bitfield.encode().len(), 3);
+}
+
 #[test]
 fn complete_proposal_approved() {
-    let mut prop = ProposalVotes {
-        votes_for: vec![1, 2],
-        votes_against: vec![3],
+    let mut prop = ProposalVotes::<u64> {
+        votes_for: vec![1, 2].into_iter().collect(),
+        votes_against: vec![3].into_iter().collect(),
         status: ProposalStatus::Initiated,
         expiry: ProposalLifetime::get(),
     };
 
-    prop.try_to_complete(2, 3);
+    prop.try_to_complete(VoteThreshold::Absolute(2), 3);
     assert_eq!(prop.status, ProposalStatus::Approved);
 }
 
 #[test]
 fn complete_proposal_rejected() {
-    let mut prop = ProposalVotes {
-        votes_for: vec![1],
-        votes_against: vec![2, 3],
+    let mut prop = ProposalVotes::<u64> {
+        votes_for: vec![1].into_iter().collect(),
+        votes_against: vec![2, 3].into_iter().collect(),
         status: ProposalStatus::Initiated,
         expiry: ProposalLifetime::get(),
     };
 
-    prop.try_to_complete(2, 3);
+    prop.try_to_complete(VoteThreshold::Absolute(2), 3);
     assert_eq!(prop.status, ProposalStatus::Rejected);
 }
 
 #[test]
 fn complete_proposal_bad_threshold() {
-    let mut prop = ProposalVotes {
-        votes_for: vec![1, 2],
-        votes_against: vec![],
+    let mut prop = ProposalVotes::<u64> {
+        votes_for: vec![1, 2].into_iter().collect(),
+        votes_against: vec![].into_iter().collect(),
+        status: ProposalStatus::Initiated,
+        expiry: ProposalLifetime::get(),
+    };
+
+    prop.try_to_complete(VoteThreshold::Absolute(3), 2);
+    assert_eq!(prop.status, ProposalStatus::Initiated);
+
+    let mut prop = ProposalVotes::<u64> {
+        votes_for: vec![].into_iter().collect(),
+        votes_against: vec![1, 2].into_iter().collect(),
         status: ProposalStatus::Initiated,
         expiry: ProposalLifetime::get(),
     };
 
-    prop.try_to_complete(3, 2);
+    prop.try_to_complete(VoteThreshold::Absolute(3), 2);
     assert_eq!(prop.status, ProposalStatus::Initiated);
+}
+
+#[test]
+fn vote_threshold() {
+    let ratio = VoteThreshold::Proportion(Perbill::from_percent(60));
+    assert_eq!(ratio.votes(0), 1);
+    assert_eq!(ratio.votes(1), 1);
+    assert_eq!(ratio.votes(2), 2);
+    assert_eq!(ratio.votes(5), 3);
+    assert_eq!(ratio.votes(6), 4);
+    assert_eq!(VoteThreshold::Absolute(2).votes(5), 2);
+}
 
-    let mut prop = ProposalVotes {
-        votes_for: vec![],
-        votes_against: vec![1, 2],
+#[test]
+fn complete_proposal_proportional() {
+    let threshold = VoteThreshold::Proportion(Perbill::from_percent(60));
+    let mut prop = ProposalVotes::<u64> {
+        votes_for: vec![1, 2].into_iter().collect(),
+        votes_against: vec![].into_iter().collect(),
         status: ProposalStatus::Initiated,
         expiry: ProposalLifetime::get(),
     };
 
-    prop.try_to_complete(3, 2);
+    prop.try_to_complete(threshold, 5);
     assert_eq!(prop.status, ProposalStatus::Initiated);
+
+    prop.try_to_complete(threshold, 3);
+    assert_eq!(prop.status, ProposalStatus::Approved);
 }
 
 #[test]
 fn setup_resources() {
     new_test_ext().execute_with(|| {
         let id: ResourceId = [1; 32];
-        let method = "Pallet.do_something".as_bytes().to_vec();
-        let method2 = "Pallet.do_somethingElse".as_bytes().to_vec();
+        let info = ResourceInfo::new(
+            ResourceKind::Generic,
+            "Pallet.do_something".as_bytes().to_vec(),
+        );
+        let info2 = ResourceInfo {
+            decimals: 18,
+            allowed_chains: vec![2],
+            ..ResourceInfo::new(
+                ResourceKind::Fungible,
+                "Pallet.do_somethingElse".as_bytes().to_vec(),
+            )
+        };
 
-        assert_ok!(Bridge::set_resource(Origin::root(), id, method.clone()));
-        assert_eq!(Bridge::resources(id), Some(method));
+        assert_ok!(Bridge::set_resource(Origin::root(), id, info.clone()));
+        assert_eq!(Bridge::resources(id), Some(info));
 
-        assert_ok!(Bridge::set_resource(Origin::root(), id, method2.clone()));
-        assert_eq!(Bridge::resources(id), Some(method2));
+        assert_ok!(Bridge::set_resource(Origin::root(), id, info2.clone()));
+        assert_eq!(Bridge::resources(id), Some(info2));
 
         assert_ok!(Bridge::remove_resource(Origin::root(), id));
         assert_eq!(Bridge::resources(id), None);
+        assert_noop!(
+            Bridge::remove_resource(Origin::root(), id),
+            Error::<Test>::ResourceDoesNotExist
+        );
+
+        assert_events(vec![
+            Event::bridge(RawEvent::ResourceRegistered(id, ResourceKind::Generic)),
+            Event::bridge(RawEvent::ResourceRegistered(id, ResourceKind::Fungible)),
+            Event::bridge(RawEvent::ResourceRemoved(id)),
+        ]);
     })
 }
 
@@ -110,19 +200,107 @@ fn set_get_threshold() {
     new_test_ext().execute_with(|| {
         assert_eq!(<RelayerThreshold>::get(), 1);
 
+        // The threshold can't exceed the number of relayers
+        assert_noop!(
+            Bridge::set_threshold(Origin::root(), TEST_THRESHOLD),
+            Error::<Test>::ThresholdExceedsRelayers
+        );
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_B));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_C));
+
         assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD));
         assert_eq!(<RelayerThreshold>::get(), TEST_THRESHOLD);
 
-        assert_ok!(Bridge::set_threshold(Origin::root(), 5));
-        assert_eq!(<RelayerThreshold>::get(), 5);
+        assert_ok!(Bridge::set_threshold(Origin::root(), 3));
+        assert_eq!(<RelayerThreshold>::get(), 3);
+        assert_noop!(
+            Bridge::set_threshold(Origin::root(), 4),
+            Error::<Test>::ThresholdExceedsRelayers
+        );
+        assert_noop!(
+            Bridge::set_threshold(Origin::root(), 0),
+            Error::<Test>::InvalidThreshold
+        );
+
+        // Relayers can't be removed while the threshold requires their votes
+        assert_noop!(
+            Bridge::remove_relayer(Origin::root(), RELAYER_A),
+            Error::<Test>::ThresholdExceedsRelayers
+        );
+        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD));
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_A));
 
         assert_events(vec![
             Event::bridge(RawEvent::RelayerThresholdChanged(TEST_THRESHOLD)),
-            Event::bridge(RawEvent::RelayerThresholdChanged(5)),
+            Event::bridge(RawEvent::RelayerThresholdChanged(3)),
+            Event::bridge(RawEvent::RelayerThresholdChanged(TEST_THRESHOLD)),
+            Event::bridge(RawEvent::RelayerRemoved(RELAYER_A)),
         ]);
     })
 }
 
+#[test]
+fn threshold_ratio() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let ratio = Perbill::from_percent(60);
+        assert_noop!(
+            Bridge::set_threshold_ratio(Origin::signed(RELAYER_A), Some(ratio)),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Bridge::set_threshold_ratio(Origin::root(), Some(Perbill::zero())),
+            Error::<Test>::InvalidThreshold
+        );
+        assert_ok!(Bridge::set_threshold_ratio(Origin::root(), Some(ratio)));
+        assert_eq!(Bridge::vote_threshold(), VoteThreshold::Proportion(ratio));
+        assert_eq!(Bridge::required_votes(), 2);
+
+        // The threshold follows the size of the relayer set, rounded up
+        assert_ok!(Bridge::add_relayer(Origin::root(), 10));
+        assert_ok!(Bridge::add_relayer(Origin::root(), 11));
+        assert_eq!(Bridge::required_votes(), 3);
+
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = Bridge::proposal_hash(&proposal);
+        let vote = |relayer| {
+            Bridge::acknowledge_proposal(
+                Origin::signed(relayer),
+                1,
+                src_id,
+                r_id,
+                Box::new(proposal.clone()),
+            )
+        };
+        assert_ok!(vote(RELAYER_A));
+        assert_ok!(vote(RELAYER_B));
+        assert_eq!(
+            Bridge::votes(src_id, (1, prop_hash)).unwrap().status,
+            ProposalStatus::Initiated
+        );
+        assert_ok!(vote(RELAYER_C));
+        assert_eq!(
+            Bridge::votes(src_id, (1, prop_hash)).unwrap().status,
+            ProposalStatus::Executed
+        );
+
+        // The absolute threshold applies again once the ratio is cleared
+        assert_ok!(Bridge::set_threshold_ratio(Origin::root(), None));
+        assert_eq!(
+            Bridge::vote_threshold(),
+            VoteThreshold::Absolute(TEST_THRESHOLD)
+        );
+        assert_eq!(Bridge::required_votes(), TEST_THRESHOLD);
+
+        assert_events(vec![Event::bridge(RawEvent::RelayerThresholdRatioChanged(
+            None,
+        ))]);
+    })
+}
+
 #[test]
 fn asset_transfer_success() {
     new_test_ext().execute_with(|| {
@@ -133,10 +311,14 @@ fn asset_transfer_success() {
         let amount = 100;
         let token_id = vec![1, 2, 3, 4];
 
-        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD,));
-
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            resource_id,
+            ResourceInfo::new(ResourceKind::Generic, b"Example.transfer".to_vec())
+        ));
         assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id.clone()));
         assert_ok!(Bridge::transfer_fungible(
+            DEPOSITOR,
             dest_id.clone(),
             resource_id.clone(),
             to.clone(),
@@ -150,10 +332,20 @@ fn asset_transfer_success() {
                 resource_id.clone(),
                 amount.into(),
                 to.clone(),
+                DEPOSITOR,
+                payload_hash(
+                    resource_id,
+                    Deposit::Fungible {
+                        amount: amount.into(),
+                        recipient: to.clone(),
+                    },
+                ),
+                0,
             )),
         ]);
 
         assert_ok!(Bridge::transfer_nonfungible(
+            DEPOSITOR,
             dest_id.clone(),
             resource_id.clone(),
             token_id.clone(),
@@ -167,9 +359,20 @@ fn asset_transfer_success() {
             token_id,
             to.clone(),
             metadata.clone(),
+            DEPOSITOR,
+            payload_hash(
+                resource_id,
+                Deposit::NonFungible {
+                    token_id: U256::from(0x01020304),
+                    recipient: to.clone(),
+                    metadata: metadata.clone(),
+                },
+            ),
+            0,
         ))]);
 
         assert_ok!(Bridge::transfer_generic(
+            DEPOSITOR,
             dest_id.clone(),
             resource_id.clone(),
             metadata.clone()
@@ -178,7 +381,10 @@ fn asset_transfer_success() {
             dest_id.clone(),
             3,
             resource_id,
-            metadata,
+            metadata.clone(),
+            DEPOSITOR,
+            payload_hash(resource_id, Deposit::Generic { metadata }),
+            0,
         ))]);
     })
 }
@@ -196,32 +402,287 @@ fn asset_transfer_invalid_chain() {
         ))]);
 
         assert_noop!(
-            Bridge::transfer_fungible(bad_dest_id, resource_id.clone(), vec![], U256::zero()),
+            Bridge::transfer_fungible(
+                DEPOSITOR,
+                bad_dest_id,
+                resource_id.clone(),
+                vec![],
+                U256::zero()
+            ),
+            Error::<Test>::ChainNotWhitelisted
+        );
+
+        assert_noop!(
+            Bridge::transfer_nonfungible(
+                DEPOSITOR,
+                bad_dest_id,
+                resource_id.clone(),
+                vec![],
+                vec![],
+                vec![]
+            ),
             Error::<Test>::ChainNotWhitelisted
         );
 
         assert_noop!(
-            Bridge::transfer_nonfungible(bad_dest_id, resource_id.clone(), vec![], vec![], vec![]),
+            Bridge::transfer_generic(DEPOSITOR, bad_dest_id, resource_id.clone(), vec![]),
             Error::<Test>::ChainNotWhitelisted
         );
+    })
+}
+
+#[test]
+fn asset_transfer_invalid_resource() {
+    new_test_ext().execute_with(|| {
+        let dest_id = 2;
+        let other_id = 3;
+        let resource_id = [1; 32];
+        let mut info = ResourceInfo::new(ResourceKind::Generic, b"Example.transfer".to_vec());
+
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), other_id));
+        assert_noop!(
+            Bridge::transfer_fungible(DEPOSITOR, dest_id, resource_id, vec![], U256::one()),
+            Error::<Test>::ResourceDoesNotExist
+        );
+
+        info.enabled = false;
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            resource_id,
+            info.clone()
+        ));
+        assert_noop!(
+            Bridge::transfer_nonfungible(DEPOSITOR, dest_id, resource_id, vec![1], vec![], vec![]),
+            Error::<Test>::ResourceDisabled
+        );
+
+        info.enabled = true;
+        info.allowed_chains = vec![other_id];
+        assert_ok!(Bridge::set_resource(Origin::root(), resource_id, info));
+        assert_noop!(
+            Bridge::transfer_generic(DEPOSITOR, dest_id, resource_id, vec![]),
+            Error::<Test>::ResourceChainNotAllowed
+        );
+        assert_ok!(Bridge::transfer_generic(
+            DEPOSITOR,
+            other_id,
+            resource_id,
+            vec![]
+        ));
+    })
+}
+
+#[test]
+fn transfer_fees() {
+    new_test_ext().execute_with(|| {
+        let dest_id = 2;
+        let resource_id = [1; 32];
+        let fee = 10;
+        let fees = Bridge::fees_account_id();
 
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            resource_id,
+            ResourceInfo::new(ResourceKind::Generic, b"Example.transfer".to_vec())
+        ));
+        assert_noop!(
+            Bridge::set_fee(Origin::signed(DEPOSITOR), dest_id, resource_id, fee),
+            DispatchError::BadOrigin
+        );
         assert_noop!(
-            Bridge::transfer_generic(bad_dest_id, resource_id.clone(), vec![]),
+            Bridge::set_fee(Origin::root(), dest_id, resource_id, fee),
             Error::<Test>::ChainNotWhitelisted
         );
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        assert_noop!(
+            Bridge::set_fee(Origin::root(), dest_id, [2; 32], fee),
+            Error::<Test>::ResourceDoesNotExist
+        );
+        assert_ok!(Bridge::set_fee(Origin::root(), dest_id, resource_id, fee));
+        assert_eq!(Bridge::bridge_fee((dest_id, resource_id)), fee);
+        // Other resources remain free
+        assert_eq!(Bridge::bridge_fee((dest_id, [2; 32])), 0);
+
+        assert_noop!(
+            Bridge::transfer_generic(DEPOSITOR, dest_id, resource_id, vec![1]),
+            balances::Error::<Test>::InsufficientBalance
+        );
+
+        // The depositor's account is kept alive
+        Balances::make_free_balance_be(&DEPOSITOR, fee);
+        assert_noop!(
+            Bridge::transfer_generic(DEPOSITOR, dest_id, resource_id, vec![1]),
+            balances::Error::<Test>::KeepAlive
+        );
+
+        Balances::make_free_balance_be(&DEPOSITOR, 100);
+        assert_ok!(Bridge::transfer_generic(
+            DEPOSITOR,
+            dest_id,
+            resource_id,
+            vec![1]
+        ));
+        assert_eq!(Balances::free_balance(DEPOSITOR), 100 - fee);
+        assert_eq!(Balances::free_balance(&fees), fee);
+        assert_events(vec![
+            Event::balances(balances::Event::Transfer(DEPOSITOR, fees, fee)),
+            Event::bridge(RawEvent::GenericTransfer(
+                dest_id,
+                1,
+                resource_id,
+                vec![1],
+                DEPOSITOR,
+                payload_hash(resource_id, Deposit::Generic { metadata: vec![1] }),
+                fee,
+            )),
+        ]);
+
+        // Collected fees are withdrawn by the admin
+        assert_noop!(
+            Bridge::withdraw_fees(Origin::signed(DEPOSITOR), DEPOSITOR, fee),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Bridge::withdraw_fees(Origin::root(), RELAYER_A, fee + 1),
+            balances::Error::<Test>::InsufficientBalance
+        );
+        // The fee account is kept alive
+        assert_noop!(
+            Bridge::withdraw_fees(Origin::root(), RELAYER_A, fee),
+            balances::Error::<Test>::KeepAlive
+        );
+        let withdrawn = fee - ExistentialDeposit::get();
+        assert_ok!(Bridge::withdraw_fees(Origin::root(), RELAYER_A, withdrawn));
+        assert_eq!(Balances::free_balance(&fees), ExistentialDeposit::get());
+        assert_events(vec![
+            Event::balances(balances::Event::Transfer(fees, RELAYER_A, withdrawn)),
+            Event::bridge(RawEvent::FeesWithdrawn(RELAYER_A, withdrawn)),
+        ]);
+
+        assert_ok!(Bridge::set_fee(Origin::root(), dest_id, resource_id, 0));
+        assert!(!<BridgeFees<Test>>::contains_key((dest_id, resource_id)));
+        assert_events(vec![Event::bridge(RawEvent::FeeChanged(
+            dest_id,
+            resource_id,
+            0,
+        ))]);
+    })
+}
+
+#[test]
+fn fees_below_existential_deposit() {
+    new_test_ext().execute_with(|| {
+        let dest_id = 2;
+        let resource_id = [1; 32];
+        mock::set_existential_deposit(20);
+
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            resource_id,
+            ResourceInfo::new(ResourceKind::Generic, b"Example.transfer".to_vec())
+        ));
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        assert_noop!(
+            Bridge::set_fee(Origin::root(), dest_id, resource_id, 19),
+            Error::<Test>::FeeBelowMinimum
+        );
+
+        // The first fee creates the fee account
+        assert_ok!(Bridge::set_fee(Origin::root(), dest_id, resource_id, 20));
+        Balances::make_free_balance_be(&DEPOSITOR, 100);
+        assert_ok!(Bridge::transfer_generic(
+            DEPOSITOR,
+            dest_id,
+            resource_id,
+            vec![1]
+        ));
+        assert_eq!(Balances::free_balance(Bridge::fees_account_id()), 20);
+    })
+}
+
+#[test]
+fn deposit_records() {
+    new_test_ext().execute_with(|| {
+        let dest_id = 2;
+        let resource_id = [1; 32];
+        let to = vec![2];
+        let amount = U256::from(100);
+
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            resource_id,
+            ResourceInfo::new(ResourceKind::Generic, b"Example.transfer".to_vec())
+        ));
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_id));
+        System::set_block_number(5);
+        assert_ok!(Bridge::transfer_fungible(
+            DEPOSITOR,
+            dest_id,
+            resource_id,
+            to.clone(),
+            amount
+        ));
+        let record = Bridge::deposit_record(dest_id, 1).unwrap();
+        assert_eq!(
+            record,
+            DepositRecord {
+                resource_id,
+                payload: record.payload.clone(),
+                depositor: DEPOSITOR,
+                block: 5,
+            }
+        );
+        assert_eq!(
+            Deposit::parse(ResourceKind::Fungible, &record.payload),
+            Some(Deposit::Fungible {
+                amount,
+                recipient: to.clone()
+            })
+        );
+
+        assert_ok!(Bridge::transfer_nonfungible(
+            DEPOSITOR,
+            dest_id,
+            resource_id,
+            vec![1, 2],
+            to.clone(),
+            vec![3]
+        ));
+        let record = Bridge::deposit_record(dest_id, 2).unwrap();
+        assert_eq!(
+            Deposit::parse(ResourceKind::NonFungible, &record.payload),
+            Some(Deposit::NonFungible {
+                token_id: U256::from(0x0102),
+                recipient: to.clone(),
+                metadata: vec![3],
+            })
+        );
+        assert_noop!(
+            Bridge::transfer_nonfungible(DEPOSITOR, dest_id, resource_id, vec![1; 33], to, vec![]),
+            Error::<Test>::InvalidTokenId
+        );
+
+        // Records are removed once their retention period has passed
+        let prune_at = 5 + DepositRecordRetention::get();
+        Bridge::on_initialize(prune_at - 1);
+        assert!(Bridge::deposit_record(dest_id, 1).is_some());
+        Bridge::on_initialize(prune_at);
+        assert_eq!(Bridge::deposit_record(dest_id, 1), None);
+        assert_eq!(Bridge::deposit_record(dest_id, 2), None);
     })
 }
 
 #[test]
 fn add_remove_relayer() {
     new_test_ext().execute_with(|| {
-        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD,));
         assert_eq!(Bridge::relayer_count(), 0);
 
         assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
         assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_B));
         assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_C));
         assert_eq!(Bridge::relayer_count(), 3);
+        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD,));
 
         // Already exists
         assert_noop!(
@@ -238,21 +699,115 @@ fn add_remove_relayer() {
         );
         assert_eq!(Bridge::relayer_count(), 2);
 
+        // The last relayer can be removed whatever the threshold
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_C));
+        assert_noop!(
+            Bridge::remove_relayer(Origin::root(), RELAYER_A),
+            Error::<Test>::ThresholdExceedsRelayers
+        );
+        assert_ok!(Bridge::set_threshold(Origin::root(), 1));
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_A));
+        assert_eq!(Bridge::relayer_count(), 0);
+        assert_eq!(Bridge::relayer_threshold(), 1);
+
         assert_events(vec![
+            Event::balances(balances::Event::Reserved(RELAYER_A, RelayerBond::get())),
             Event::bridge(RawEvent::RelayerAdded(RELAYER_A)),
+            Event::balances(balances::Event::Reserved(RELAYER_B, RelayerBond::get())),
             Event::bridge(RawEvent::RelayerAdded(RELAYER_B)),
+            Event::balances(balances::Event::Reserved(RELAYER_C, RelayerBond::get())),
             Event::bridge(RawEvent::RelayerAdded(RELAYER_C)),
+            Event::bridge(RawEvent::RelayerThresholdChanged(TEST_THRESHOLD)),
             Event::bridge(RawEvent::RelayerRemoved(RELAYER_B)),
+            Event::bridge(RawEvent::RelayerRemoved(RELAYER_C)),
+            Event::bridge(RawEvent::RelayerThresholdChanged(1)),
+            Event::bridge(RawEvent::RelayerRemoved(RELAYER_A)),
         ]);
     })
 }
 
-fn make_proposal(r: Vec<u8>) -> mock::Call {
-    Call::System(system::Call::remark(r))
+#[test]
+fn relayer_set_is_bounded() {
+    new_test_ext().execute_with(|| {
+        for relayer in 0..MaxRelayers::get() {
+            assert_ok!(Bridge::add_relayer(Origin::root(), relayer as u64 + 10));
+        }
+        assert_noop!(
+            Bridge::add_relayer(Origin::root(), RELAYER_A),
+            Error::<Test>::TooManyRelayers
+        );
+
+        assert_ok!(Bridge::remove_relayer(Origin::root(), 10));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        assert_eq!(Bridge::relayer_count(), MaxRelayers::get());
+    })
 }
 
 #[test]
-fn create_sucessful_proposal() {
+fn relayers_are_assigned_free_slots() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_B));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_C));
+        assert_eq!(Bridge::relayer_slot(RELAYER_A), Some(0));
+        assert_eq!(Bridge::relayer_slot(RELAYER_B), Some(1));
+        assert_eq!(Bridge::relayer_slot(RELAYER_C), Some(2));
+
+        // Freed slots are reused once their proposals have been removed
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_B));
+        assert_eq!(Bridge::relayer_slot(RELAYER_B), None);
+        assert_eq!(Bridge::occupied_slots(), vec![0, 2].into_iter().collect());
+        assert_eq!(Bridge::slot_freed_at(1), Some(1));
+        assert_ok!(Bridge::add_relayer(Origin::root(), 10));
+        assert_eq!(Bridge::relayer_slot(10), Some(3));
+
+        System::set_block_number(1 + ProposalLifetime::get() + ProposalRetention::get());
+        assert_ok!(Bridge::add_relayer(Origin::root(), 11));
+        assert_eq!(Bridge::relayer_slot(11), Some(1));
+        assert_eq!(Bridge::slot_freed_at(1), None);
+        assert_eq!(Bridge::occupied_slots().count(), Bridge::relayer_count());
+    })
+}
+
+#[test]
+fn relayer_slots_stay_below_max_relayers() {
+    new_test_ext().execute_with(|| {
+        for relayer in 10..10 + MaxRelayers::get() as u64 {
+            assert_ok!(Bridge::add_relayer(Origin::root(), relayer));
+        }
+
+        // Replacing a relayer waits for its slot to be freed
+        assert_ok!(Bridge::remove_relayer(Origin::root(), 10));
+        assert_noop!(
+            Bridge::add_relayer(Origin::root(), RELAYER_A),
+            Error::<Test>::SlotCoolingDown
+        );
+        assert_eq!(Bridge::bond(RELAYER_A), None);
+
+        System::set_block_number(1 + ProposalLifetime::get() + ProposalRetention::get());
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        assert_eq!(Bridge::relayer_slot(RELAYER_A), Some(0));
+
+        // Repeated removals and additions keep reusing the same slots
+        for _ in 0..3 {
+            assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_A));
+            assert_noop!(
+                Bridge::add_relayer(Origin::root(), RELAYER_A),
+                Error::<Test>::SlotCoolingDown
+            );
+            let now = System::block_number();
+            System::set_block_number(now + ProposalLifetime::get() + ProposalRetention::get());
+            assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+            assert_eq!(Bridge::relayer_slot(RELAYER_A), Some(0));
+        }
+        assert!(Bridge::occupied_slots()
+            .iter()
+            .all(|slot| slot < MaxRelayers::get()));
+    })
+}
+
+#[test]
+fn freed_slot_votes_are_not_taken_over() {
     let src_id = 1;
     let r_id = derive_resource_id(src_id, b"remark");
 
@@ -260,7 +815,6 @@ fn create_sucessful_proposal() {
         let prop_id = 1;
         let proposal = make_proposal(vec![10]);
 
-        // Create proposal (& vote)
         assert_ok!(Bridge::acknowledge_proposal(
             Origin::signed(RELAYER_A),
             prop_id,
@@ -268,67 +822,384 @@ fn create_sucessful_proposal() {
             r_id,
             Box::new(proposal.clone())
         ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
-        let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![],
-            status: ProposalStatus::Initiated,
-            expiry: ProposalLifetime::get() + 1,
-        };
-        assert_eq!(prop, expected);
-
-        // Second relayer votes against
-        assert_ok!(Bridge::reject_proposal(
-            Origin::signed(RELAYER_B),
-            prop_id,
-            src_id,
-            r_id,
-            Box::new(proposal.clone())
-        ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
-        let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![RELAYER_B],
-            status: ProposalStatus::Initiated,
-            expiry: ProposalLifetime::get() + 1,
-        };
-        assert_eq!(prop, expected);
+        assert!(!Bridge::can_vote(&RELAYER_A, src_id, prop_id, &proposal));
 
-        // Third relayer votes in favour
+        // The relayer replacing RELAYER_A is assigned another slot and votes on its own
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_A));
+        assert_ok!(Bridge::add_relayer(Origin::root(), 10));
+        assert_eq!(Bridge::relayer_slot(10), Some(3));
+        assert!(Bridge::can_vote(&10, src_id, prop_id, &proposal));
         assert_ok!(Bridge::acknowledge_proposal(
-            Origin::signed(RELAYER_C),
+            Origin::signed(10),
             prop_id,
             src_id,
             r_id,
             Box::new(proposal.clone())
         ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
-        let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A, RELAYER_C],
-            votes_against: vec![RELAYER_B],
-            status: ProposalStatus::Approved,
-            expiry: ProposalLifetime::get() + 1,
+        assert_eq!(
+            Bridge::proposal_votes(src_id, prop_id, &proposal)
+                .unwrap()
+                .status,
+            ProposalStatus::Executed
+        );
+
+        // The slot is reassigned once the proposals it voted on have been removed
+        let reusable_at = 1 + ProposalLifetime::get() + ProposalRetention::get();
+        Bridge::on_initialize(reusable_at);
+        System::set_block_number(reusable_at);
+        assert_eq!(Bridge::proposal_votes(src_id, prop_id, &proposal), None);
+        assert_ok!(Bridge::add_relayer(Origin::root(), 11));
+        assert_eq!(Bridge::relayer_slot(11), Some(0));
+    })
+}
+
+#[test]
+fn relayers_are_bonded() {
+    new_test_ext().execute_with(|| {
+        let bond = RelayerBond::get();
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        assert_eq!(Balances::reserved_balance(RELAYER_A), bond);
+        assert_eq!(
+            Bridge::bond(RELAYER_A),
+            Some(Bond {
+                amount: bond,
+                slot: 0,
+                since: 1,
+                until: None,
+            })
+        );
+        assert_noop!(
+            Bridge::withdraw_unbonded(Origin::signed(RELAYER_A)),
+            Error::<Test>::RelayerStillRegistered
+        );
+        // Meets the threshold while RELAYER_A is removed
+        assert_ok!(Bridge::add_relayer(Origin::root(), 10));
+        assert_noop!(
+            Bridge::withdraw_unbonded(Origin::signed(RELAYER_B)),
+            Error::<Test>::NotBonded
+        );
+        assert_noop!(
+            Bridge::add_relayer(Origin::root(), DEPOSITOR),
+            balances::Error::<Test>::InsufficientBalance
+        );
+
+        // The bond stays reserved until the unbonding period has passed
+        System::set_block_number(5);
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_A));
+        assert_eq!(Bridge::bond(RELAYER_A).unwrap().until, Some(5));
+        System::set_block_number(5 + UnbondingDelay::get());
+        assert_noop!(
+            Bridge::withdraw_unbonded(Origin::signed(RELAYER_A)),
+            Error::<Test>::UnbondingNotFinished
+        );
+        // Proposals created before the removal can be reported until they are removed
+        let period = ProposalLifetime::get() + ProposalRetention::get();
+        assert_eq!(Bridge::unbonding_period(), period);
+        System::set_block_number(5 + period - 1);
+        assert_noop!(
+            Bridge::withdraw_unbonded(Origin::signed(RELAYER_A)),
+            Error::<Test>::UnbondingNotFinished
+        );
+
+        // Adding the relayer again keeps its bond
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        assert_eq!(Balances::reserved_balance(RELAYER_A), bond);
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_A));
+
+        System::set_block_number(System::block_number() + period);
+        assert_ok!(Bridge::withdraw_unbonded(Origin::signed(RELAYER_A)));
+        assert_eq!(Bridge::bond(RELAYER_A), None);
+        assert_eq!(Balances::reserved_balance(RELAYER_A), 0);
+        assert_eq!(Balances::free_balance(RELAYER_A), bond);
+
+        assert_events(vec![
+            Event::bridge(RawEvent::RelayerRemoved(RELAYER_A)),
+            Event::balances(balances::Event::Unreserved(RELAYER_A, bond)),
+            Event::bridge(RawEvent::BondWithdrawn(RELAYER_A, bond)),
+        ]);
+    })
+}
+
+#[test]
+fn report_misbehaviour() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        let conflicting = make_proposal(vec![11]);
+        let conflicting_hash = Bridge::proposal_hash(&conflicting);
+        let vote = |relayer, proposal: &mock::Call| {
+            Bridge::acknowledge_proposal(
+                Origin::signed(relayer),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone()),
+            )
         };
-        assert_eq!(prop, expected);
+        let report = |relayer, prop_hash| {
+            Bridge::report_misbehaviour(
+                Origin::signed(DEPOSITOR),
+                relayer,
+                src_id,
+                prop_id,
+                prop_hash,
+            )
+        };
+
+        // RELAYER_A votes for both proposals of the deposit
+        System::set_block_number(2);
+        assert_ok!(vote(RELAYER_A, &conflicting));
+        assert_noop!(
+            report(RELAYER_A, conflicting_hash),
+            Error::<Test>::InvalidReport
+        );
+        assert_ok!(vote(RELAYER_A, &proposal));
+        assert_ok!(vote(RELAYER_B, &proposal));
+
+        assert_noop!(
+            report(RELAYER_B, conflicting_hash),
+            Error::<Test>::InvalidReport
+        );
+        assert_noop!(
+            report(RELAYER_A, Bridge::proposal_hash(&proposal)),
+            Error::<Test>::InvalidReport
+        );
+        assert_noop!(
+            report(RELAYER_A, Bridge::proposal_hash(&make_proposal(vec![12]))),
+            Error::<Test>::ProposalDoesNotExist
+        );
+
+        let issuance = Balances::total_issuance();
+        assert_ok!(report(RELAYER_A, conflicting_hash));
+        assert_eq!(Balances::reserved_balance(RELAYER_A), 0);
+        assert_eq!(Balances::total_issuance(), issuance - RelayerBond::get());
+        assert_noop!(
+            report(RELAYER_A, conflicting_hash),
+            Error::<Test>::NotBonded
+        );
+
+        // The votes of the slot can't be attributed to the relayer assigned another slot
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_A));
+        assert_ok!(Bridge::add_relayer(Origin::root(), 10));
+        assert_eq!(Bridge::relayer_slot(10), Some(3));
+        assert_noop!(report(10, conflicting_hash), Error::<Test>::InvalidReport);
 
         assert_events(vec![
-            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A)),
-            Event::bridge(RawEvent::VoteAgainst(src_id, prop_id, RELAYER_B)),
-            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_C)),
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_B)),
             Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
             Event::bridge(RawEvent::ProposalSucceeded(src_id, prop_id)),
+            Event::bridge(RawEvent::RelayerSlashed(RELAYER_A, RelayerBond::get())),
+            Event::bridge(RawEvent::RelayerRemoved(RELAYER_A)),
+            Event::balances(balances::Event::Reserved(10, RelayerBond::get())),
+            Event::bridge(RawEvent::RelayerAdded(10)),
         ]);
     })
 }
 
 #[test]
-fn create_unsucessful_proposal() {
+fn report_misbehaviour_of_other_kind() {
     let src_id = 1;
-    let r_id = derive_resource_id(src_id, b"transfer");
+    let r_id = derive_resource_id(src_id, b"remark");
 
     new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
         let prop_id = 1;
-        let proposal = make_proposal(vec![11]);
+        let proposal = make_proposal(vec![10]);
+        let data = Deposit::Generic {
+            metadata: vec![1, 2, 3],
+        }
+        .to_data();
+        let conflicting = make_proposal(vec![11]);
+        let report = |prop_hash| {
+            Bridge::report_misbehaviour(
+                Origin::signed(DEPOSITOR),
+                RELAYER_A,
+                src_id,
+                prop_id,
+                prop_hash,
+            )
+        };
+
+        // RELAYER_A votes for the deposit in both forms, and later for a conflicting call
+        assert_ok!(Bridge::acknowledge_deposit(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            data.clone()
+        ));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        System::set_block_number(10);
+        for (relayer, call) in vec![(RELAYER_A, &conflicting), (RELAYER_B, &proposal)] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(relayer),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(call.clone())
+            ));
+        }
+
+        // The deposit proposal doesn't conflict with the executed call
+        assert_noop!(
+            report(Bridge::deposit_hash(r_id, &data)),
+            Error::<Test>::InvalidReport
+        );
+
+        // Reports are refused once the executed proposal is removed
+        let prune_at = 1 + ProposalLifetime::get() + ProposalRetention::get();
+        Bridge::on_initialize(prune_at);
+        assert_eq!(Bridge::proposal_votes(src_id, prop_id, &proposal), None);
+        assert!(Bridge::proposal_votes(src_id, prop_id, &conflicting).is_some());
+        assert_noop!(
+            report(Bridge::proposal_hash(&conflicting)),
+            Error::<Test>::InvalidReport
+        );
+    })
+}
+
+#[test]
+fn relayers_earn_rewards() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let pot = Bridge::rewards_account_id();
+        Balances::make_free_balance_be(&pot, 100);
+        let proposal = make_proposal(vec![10]);
+        let rejected = make_proposal(vec![11]);
+
+        // Votes for executed proposals earn a point, others don't
+        for relayer in &[RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(*relayer),
+                1,
+                src_id,
+                r_id,
+                Box::new(proposal.clone()),
+            ));
+            assert_ok!(Bridge::reject_proposal(
+                Origin::signed(*relayer),
+                2,
+                src_id,
+                r_id,
+                Box::new(rejected.clone()),
+            ));
+        }
+        assert_eq!(Bridge::reward_points(RELAYER_A), 1);
+        assert_eq!(Bridge::reward_points(RELAYER_B), 1);
+        assert_eq!(Bridge::total_reward_points(), 2);
+        assert_noop!(
+            Bridge::claim_rewards(Origin::signed(RELAYER_C)),
+            Error::<Test>::NoRewards
+        );
+
+        let balance = Balances::free_balance(RELAYER_A);
+        assert_ok!(Bridge::claim_rewards(Origin::signed(RELAYER_A)));
+        assert_eq!(Balances::free_balance(RELAYER_A), balance + 50);
+        assert_eq!(Balances::free_balance(&pot), 50);
+        assert_eq!(Bridge::reward_points(RELAYER_A), 0);
+        assert_eq!(Bridge::total_reward_points(), 1);
+        assert_noop!(
+            Bridge::claim_rewards(Origin::signed(RELAYER_A)),
+            Error::<Test>::NoRewards
+        );
+        assert_events(vec![
+            Event::balances(balances::Event::Transfer(pot, RELAYER_A, 50)),
+            Event::bridge(RawEvent::RewardsClaimed(RELAYER_A, 50)),
+        ]);
+
+        // The last claim empties the pot
+        assert_ok!(Bridge::claim_rewards(Origin::signed(RELAYER_B)));
+        assert_eq!(Balances::free_balance(&pot), 0);
+        assert_eq!(Bridge::total_reward_points(), 0);
+    })
+}
+
+#[test]
+fn rewards_are_credited_to_voters_at_execution() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+        let pending = make_proposal(vec![11]);
+        let vote = |relayer, nonce, call: &mock::Call| {
+            Bridge::acknowledge_proposal(
+                Origin::signed(relayer),
+                nonce,
+                src_id,
+                r_id,
+                Box::new(call.clone()),
+            )
+        };
+        assert_ok!(Bridge::set_threshold(Origin::root(), 3));
+        assert_ok!(vote(RELAYER_A, 1, &proposal));
+        assert_ok!(vote(RELAYER_A, 2, &pending));
+        assert_ok!(vote(RELAYER_B, 2, &pending));
+
+        // Relayers added after the proposal was created are credited for their votes
+        System::set_block_number(5);
+        assert_ok!(Bridge::add_relayer(Origin::root(), 10));
+        assert_ok!(vote(10, 1, &proposal));
+        assert_ok!(vote(RELAYER_B, 1, &proposal));
+        assert_eq!(
+            Bridge::proposal_votes(src_id, 1, &proposal).unwrap().status,
+            ProposalStatus::Executed
+        );
+        assert_eq!(Bridge::reward_points(RELAYER_A), 1);
+        assert_eq!(Bridge::reward_points(RELAYER_B), 1);
+        assert_eq!(Bridge::reward_points(10), 1);
+        assert_eq!(Bridge::reward_points(RELAYER_C), 0);
+        assert_eq!(Bridge::total_reward_points(), 3);
+
+        // Every voter is credited when a lowered threshold executes the proposal
+        assert_ok!(Bridge::set_threshold(Origin::root(), 1));
+        assert_ok!(Bridge::eval_vote_state(
+            Origin::signed(RELAYER_C),
+            2,
+            src_id,
+            Box::new(pending.clone())
+        ));
+        assert_eq!(
+            Bridge::proposal_votes(src_id, 2, &pending).unwrap().status,
+            ProposalStatus::Executed
+        );
+        assert_eq!(Bridge::reward_points(RELAYER_A), 2);
+        assert_eq!(Bridge::reward_points(RELAYER_B), 2);
+        assert_eq!(Bridge::reward_points(RELAYER_C), 0);
+        assert_eq!(Bridge::total_reward_points(), 5);
+    })
+}
+
+/// Slots of the given relayers
+fn slots(relayers: &[u64]) -> Bitfield {
+    relayers
+        .iter()
+        .map(|r| Bridge::relayer_slot(r).expect("must be a relayer"))
+        .collect()
+}
+
+fn make_proposal(r: Vec<u8>) -> mock::Call {
+    Call::System(system::Call::remark(r))
+}
+
+#[test]
+fn create_sucessful_proposal() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
 
         // Create proposal (& vote)
         assert_ok!(Bridge::acknowledge_proposal(
@@ -338,10 +1209,10 @@ fn create_unsucessful_proposal() {
             r_id,
             Box::new(proposal.clone())
         ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let prop = Bridge::votes(src_id, (prop_id, Bridge::proposal_hash(&proposal))).unwrap();
         let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![],
+            votes_for: slots(&[RELAYER_A]),
+            votes_against: Bitfield::default(),
             status: ProposalStatus::Initiated,
             expiry: ProposalLifetime::get() + 1,
         };
@@ -355,49 +1226,44 @@ fn create_unsucessful_proposal() {
             r_id,
             Box::new(proposal.clone())
         ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let prop = Bridge::votes(src_id, (prop_id, Bridge::proposal_hash(&proposal))).unwrap();
         let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![RELAYER_B],
+            votes_for: slots(&[RELAYER_A]),
+            votes_against: slots(&[RELAYER_B]),
             status: ProposalStatus::Initiated,
             expiry: ProposalLifetime::get() + 1,
         };
         assert_eq!(prop, expected);
 
-        // Third relayer votes against
-        assert_ok!(Bridge::reject_proposal(
+        // Third relayer votes in favour
+        assert_ok!(Bridge::acknowledge_proposal(
             Origin::signed(RELAYER_C),
             prop_id,
             src_id,
             r_id,
             Box::new(proposal.clone())
         ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let prop = Bridge::votes(src_id, (prop_id, Bridge::proposal_hash(&proposal))).unwrap();
         let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![RELAYER_B, RELAYER_C],
-            status: ProposalStatus::Rejected,
+            votes_for: slots(&[RELAYER_A, RELAYER_C]),
+            votes_against: slots(&[RELAYER_B]),
+            status: ProposalStatus::Executed,
             expiry: ProposalLifetime::get() + 1,
         };
         assert_eq!(prop, expected);
 
-        assert_eq!(Balances::free_balance(RELAYER_B), 0);
-        assert_eq!(
-            Balances::free_balance(Bridge::account_id()),
-            ENDOWED_BALANCE
-        );
-
         assert_events(vec![
             Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A)),
             Event::bridge(RawEvent::VoteAgainst(src_id, prop_id, RELAYER_B)),
-            Event::bridge(RawEvent::VoteAgainst(src_id, prop_id, RELAYER_C)),
-            Event::bridge(RawEvent::ProposalRejected(src_id, prop_id)),
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_C)),
+            Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, prop_id)),
         ]);
     })
 }
 
 #[test]
-fn execute_after_threshold_change() {
+fn create_unsucessful_proposal() {
     let src_id = 1;
     let r_id = derive_resource_id(src_id, b"transfer");
 
@@ -413,31 +1279,45 @@ fn execute_after_threshold_change() {
             r_id,
             Box::new(proposal.clone())
         ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let prop = Bridge::votes(src_id, (prop_id, Bridge::proposal_hash(&proposal))).unwrap();
         let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![],
+            votes_for: slots(&[RELAYER_A]),
+            votes_against: Bitfield::default(),
             status: ProposalStatus::Initiated,
             expiry: ProposalLifetime::get() + 1,
         };
         assert_eq!(prop, expected);
 
-        // Change threshold
-        assert_ok!(Bridge::set_threshold(Origin::root(), 1));
-
-        // Attempt to execute
-        assert_ok!(Bridge::eval_vote_state(
-            Origin::signed(RELAYER_A),
+        // Second relayer votes against
+        assert_ok!(Bridge::reject_proposal(
+            Origin::signed(RELAYER_B),
             prop_id,
             src_id,
+            r_id,
             Box::new(proposal.clone())
         ));
+        let prop = Bridge::votes(src_id, (prop_id, Bridge::proposal_hash(&proposal))).unwrap();
+        let expected = ProposalVotes {
+            votes_for: slots(&[RELAYER_A]),
+            votes_against: slots(&[RELAYER_B]),
+            status: ProposalStatus::Initiated,
+            expiry: ProposalLifetime::get() + 1,
+        };
+        assert_eq!(prop, expected);
 
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        // Third relayer votes against
+        assert_ok!(Bridge::reject_proposal(
+            Origin::signed(RELAYER_C),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        let prop = Bridge::votes(src_id, (prop_id, Bridge::proposal_hash(&proposal))).unwrap();
         let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![],
-            status: ProposalStatus::Approved,
+            votes_for: slots(&[RELAYER_A]),
+            votes_against: slots(&[RELAYER_B, RELAYER_C]),
+            status: ProposalStatus::Rejected,
             expiry: ProposalLifetime::get() + 1,
         };
         assert_eq!(prop, expected);
@@ -450,59 +1330,350 @@ fn execute_after_threshold_change() {
 
         assert_events(vec![
             Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A)),
-            Event::bridge(RawEvent::RelayerThresholdChanged(1)),
-            Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
-            Event::bridge(RawEvent::ProposalSucceeded(src_id, prop_id)),
+            Event::bridge(RawEvent::VoteAgainst(src_id, prop_id, RELAYER_B)),
+            Event::bridge(RawEvent::VoteAgainst(src_id, prop_id, RELAYER_C)),
+            Event::bridge(RawEvent::ProposalRejected(src_id, prop_id)),
         ]);
     })
 }
 
 #[test]
-fn proposal_expires() {
+fn vote_in_batches() {
     let src_id = 1;
     let r_id = derive_resource_id(src_id, b"remark");
 
     new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
-        let prop_id = 1;
         let proposal = make_proposal(vec![10]);
+        let other = make_proposal(vec![11]);
+        let unknown_r_id = derive_resource_id(src_id, b"unknown");
+        let relayers = Bridge::relayer_count();
 
-        // Create proposal (& vote)
-        assert_ok!(Bridge::acknowledge_proposal(
+        // Failed votes don't affect the rest of the batch
+        let post_info = Bridge::acknowledge_proposals_batch(
             Origin::signed(RELAYER_A),
-            prop_id,
-            src_id,
-            r_id,
-            Box::new(proposal.clone())
-        ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
-        let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![],
-            status: ProposalStatus::Initiated,
-            expiry: ProposalLifetime::get() + 1,
-        };
-        assert_eq!(prop, expected);
+            vec![
+                (1, src_id, r_id, proposal.clone()),
+                (2, src_id, unknown_r_id, other.clone()),
+                (1, src_id, r_id, proposal.clone()),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            post_info.actual_weight,
+            Some(<Test as Config>::WeightInfo::acknowledge_proposals_batch(
+                relayers, 3
+            ))
+        );
+        assert_eq!(post_info.pays_fee, Pays::Yes);
+        assert_eq!(
+            Bridge::votes(src_id, (1, Bridge::proposal_hash(&proposal)))
+                .unwrap()
+                .votes_for,
+            slots(&[RELAYER_A])
+        );
+        assert!(Bridge::votes(src_id, (2, Bridge::proposal_hash(&other))).is_none());
 
-        // Increment enough blocks such that now == expiry
-        System::set_block_number(ProposalLifetime::get() + 1);
+        // Batch with only successful votes is free and includes the weight of executed calls
+        let post_info = Bridge::acknowledge_proposals_batch(
+            Origin::signed(RELAYER_B),
+            vec![
+                (1, src_id, r_id, proposal.clone()),
+                (2, src_id, r_id, other.clone()),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            post_info.actual_weight,
+            Some(
+                <Test as Config>::WeightInfo::acknowledge_proposals_batch(relayers, 2)
+                    + proposal.get_dispatch_info().weight
+            )
+        );
+        assert_eq!(post_info.pays_fee, Pays::No);
+
+        assert_ok!(Bridge::reject_proposals_batch(
+            Origin::signed(RELAYER_C),
+            vec![(2, src_id, r_id, other.clone())],
+        ));
+        assert_eq!(
+            Bridge::votes(src_id, (2, Bridge::proposal_hash(&other))).unwrap(),
+            ProposalVotes {
+                votes_for: slots(&[RELAYER_B]),
+                votes_against: slots(&[RELAYER_C]),
+                status: ProposalStatus::Initiated,
+                expiry: ProposalLifetime::get() + 1,
+            }
+        );
 
-        // Attempt to submit a vote should fail
         assert_noop!(
-            Bridge::reject_proposal(
-                Origin::signed(RELAYER_B),
-                prop_id,
-                src_id,
-                r_id,
-                Box::new(proposal.clone())
+            Bridge::acknowledge_proposals_batch(
+                Origin::signed(RELAYER_C),
+                vec![(3, src_id, r_id, proposal.clone()); 4],
             ),
-            Error::<Test>::ProposalExpired
+            Error::<Test>::BatchTooLarge
+        );
+        assert_noop!(
+            Bridge::reject_proposals_batch(Origin::signed(DEPOSITOR), vec![]),
+            Error::<Test>::MustBeRelayer
         );
 
-        // Proposal state should remain unchanged
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, 1, RELAYER_A)),
+            Event::bridge(RawEvent::BatchVoteSucceeded(src_id, 1)),
+            Event::bridge(RawEvent::BatchVoteFailed(
+                src_id,
+                2,
+                Error::<Test>::ResourceDoesNotExist.into(),
+            )),
+            Event::bridge(RawEvent::BatchVoteFailed(
+                src_id,
+                1,
+                Error::<Test>::RelayerAlreadyVoted.into(),
+            )),
+            Event::bridge(RawEvent::VoteFor(src_id, 1, RELAYER_B)),
+            Event::bridge(RawEvent::ProposalApproved(src_id, 1)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, 1)),
+            Event::bridge(RawEvent::BatchVoteSucceeded(src_id, 1)),
+            Event::bridge(RawEvent::VoteFor(src_id, 2, RELAYER_B)),
+            Event::bridge(RawEvent::BatchVoteSucceeded(src_id, 2)),
+            Event::bridge(RawEvent::VoteAgainst(src_id, 2, RELAYER_C)),
+            Event::bridge(RawEvent::BatchVoteSucceeded(src_id, 2)),
+        ]);
+    })
+}
+
+#[test]
+fn submit_signed_proposal() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = Bridge::proposal_hash(&proposal);
+        let payload = Bridge::signed_proposal_payload(src_id, prop_id, r_id, prop_hash);
+        let submit = |signatures: Vec<(u64, MultiSignature)>| {
+            Bridge::submit_signed_proposal(
+                Origin::signed(DEPOSITOR),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone()),
+                signatures,
+            )
+        };
+
+        // Relayers sign with keys of any supported scheme
+        let key_a = sr25519::Pair::from_seed(&[1; 32]);
+        let key_b = ed25519::Pair::from_seed(&[2; 32]);
+        let key_c = ecdsa::Pair::from_seed(&[3; 32]);
+        assert_ok!(Bridge::set_relayer_key(
+            Origin::signed(RELAYER_A),
+            key_a.public().into()
+        ));
+        assert_ok!(Bridge::set_relayer_key(
+            Origin::signed(RELAYER_B),
+            key_b.public().into()
+        ));
+        assert_ok!(Bridge::set_relayer_key(
+            Origin::signed(RELAYER_C),
+            key_c.public().into()
+        ));
+        assert_noop!(
+            Bridge::set_relayer_key(Origin::signed(DEPOSITOR), key_a.public().into()),
+            Error::<Test>::MustBeRelayer
+        );
+        let sig_a: MultiSignature = key_a.sign(&payload).into();
+        let sig_b: MultiSignature = key_b.sign(&payload).into();
+        let sig_c: MultiSignature = key_c.sign(&payload).into();
+
+        assert_noop!(
+            submit(vec![(RELAYER_A, sig_a.clone())]),
+            Error::<Test>::InsufficientSignatures
+        );
+        assert_noop!(
+            submit(vec![(RELAYER_A, sig_a.clone()), (RELAYER_A, sig_a.clone())]),
+            Error::<Test>::DuplicateSignature
+        );
+        assert_noop!(
+            submit(vec![(RELAYER_A, sig_a.clone()), (RELAYER_B, sig_c.clone())]),
+            Error::<Test>::InvalidSignature
+        );
+        assert_noop!(
+            submit(vec![(RELAYER_A, sig_a.clone()), (DEPOSITOR, sig_b.clone())]),
+            Error::<Test>::MustBeRelayer
+        );
+        // Signatures are bound to the nonce of the proposal
+        let other_payload = Bridge::signed_proposal_payload(src_id, prop_id + 1, r_id, prop_hash);
+        assert_noop!(
+            submit(vec![
+                (RELAYER_A, sig_a.clone()),
+                (RELAYER_B, key_b.sign(&other_payload).into())
+            ]),
+            Error::<Test>::InvalidSignature
+        );
+
+        // Signatures are combined with the votes already committed
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_noop!(
+            submit(vec![(RELAYER_B, sig_b.clone()), (RELAYER_C, sig_c.clone())]),
+            Error::<Test>::RelayerAlreadyVoted
+        );
+        assert_ok!(submit(vec![(RELAYER_C, sig_c.clone())]));
+        assert_eq!(
+            Bridge::votes(src_id, (prop_id, prop_hash)).unwrap(),
+            ProposalVotes {
+                votes_for: slots(&[RELAYER_B, RELAYER_C]),
+                votes_against: Bitfield::default(),
+                status: ProposalStatus::Executed,
+                expiry: ProposalLifetime::get() + 1,
+            }
+        );
+
+        // Keys are removed along with the relayer
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_A));
+        assert_eq!(Bridge::relayer_key(RELAYER_A), None);
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        let payload = Bridge::signed_proposal_payload(src_id, prop_id + 1, r_id, prop_hash);
+        assert_noop!(
+            Bridge::submit_signed_proposal(
+                Origin::signed(DEPOSITOR),
+                prop_id + 1,
+                src_id,
+                r_id,
+                Box::new(proposal.clone()),
+                vec![
+                    (RELAYER_A, key_a.sign(&payload).into()),
+                    (RELAYER_B, key_b.sign(&payload).into())
+                ],
+            ),
+            Error::<Test>::RelayerKeyNotSet
+        );
+
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_B)),
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_C)),
+            Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, prop_id)),
+            Event::bridge(RawEvent::RelayerRemoved(RELAYER_A)),
+            Event::bridge(RawEvent::RelayerAdded(RELAYER_A)),
+        ]);
+    })
+}
+
+#[test]
+fn execute_after_threshold_change() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"transfer");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![11]);
+
+        // Create proposal (& vote)
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        let prop = Bridge::votes(src_id, (prop_id, Bridge::proposal_hash(&proposal))).unwrap();
         let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![],
+            votes_for: slots(&[RELAYER_A]),
+            votes_against: Bitfield::default(),
+            status: ProposalStatus::Initiated,
+            expiry: ProposalLifetime::get() + 1,
+        };
+        assert_eq!(prop, expected);
+
+        // Change threshold
+        assert_ok!(Bridge::set_threshold(Origin::root(), 1));
+
+        // Attempt to execute
+        assert_ok!(Bridge::eval_vote_state(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            Box::new(proposal.clone())
+        ));
+
+        let prop = Bridge::votes(src_id, (prop_id, Bridge::proposal_hash(&proposal))).unwrap();
+        let expected = ProposalVotes {
+            votes_for: slots(&[RELAYER_A]),
+            votes_against: Bitfield::default(),
+            status: ProposalStatus::Executed,
+            expiry: ProposalLifetime::get() + 1,
+        };
+        assert_eq!(prop, expected);
+
+        assert_eq!(Balances::free_balance(RELAYER_B), 0);
+        assert_eq!(
+            Balances::free_balance(Bridge::account_id()),
+            ENDOWED_BALANCE
+        );
+
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A)),
+            Event::bridge(RawEvent::RelayerThresholdChanged(1)),
+            Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, prop_id)),
+        ]);
+    })
+}
+
+#[test]
+fn proposal_expires() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+
+        // Create proposal (& vote)
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        let prop = Bridge::votes(src_id, (prop_id, Bridge::proposal_hash(&proposal))).unwrap();
+        let expected = ProposalVotes {
+            votes_for: slots(&[RELAYER_A]),
+            votes_against: Bitfield::default(),
+            status: ProposalStatus::Initiated,
+            expiry: ProposalLifetime::get() + 1,
+        };
+        assert_eq!(prop, expected);
+
+        // Increment enough blocks such that now == expiry
+        System::set_block_number(ProposalLifetime::get() + 1);
+
+        // Attempt to submit a vote should fail
+        assert_noop!(
+            Bridge::reject_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ProposalExpired
+        );
+
+        // Proposal state should remain unchanged
+        let prop = Bridge::votes(src_id, (prop_id, Bridge::proposal_hash(&proposal))).unwrap();
+        let expected = ProposalVotes {
+            votes_for: slots(&[RELAYER_A]),
+            votes_against: Bitfield::default(),
             status: ProposalStatus::Initiated,
             expiry: ProposalLifetime::get() + 1,
         };
@@ -518,10 +1689,10 @@ fn proposal_expires() {
             ),
             Error::<Test>::ProposalExpired
         );
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let prop = Bridge::votes(src_id, (prop_id, Bridge::proposal_hash(&proposal))).unwrap();
         let expected = ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![],
+            votes_for: slots(&[RELAYER_A]),
+            votes_against: Bitfield::default(),
             status: ProposalStatus::Initiated,
             expiry: ProposalLifetime::get() + 1,
         };
@@ -532,3 +1703,1765 @@ fn proposal_expires() {
         ))]);
     })
 }
+
+#[test]
+fn proposal_weight_refunded_until_executed() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        let relayers = Bridge::relayer_count();
+
+        // First vote does not reach the threshold, only the vote is charged
+        let post_info = Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone()),
+        )
+        .unwrap();
+        assert_eq!(
+            post_info.actual_weight,
+            Some(<Test as Config>::WeightInfo::acknowledge_proposal(relayers))
+        );
+        assert_eq!(post_info.pays_fee, Pays::No);
+
+        // Second vote executes the proposal and includes its weight
+        let post_info = Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone()),
+        )
+        .unwrap();
+        assert_eq!(
+            post_info.actual_weight,
+            Some(
+                <Test as Config>::WeightInfo::acknowledge_proposal(relayers)
+                    + proposal.get_dispatch_info().weight
+            )
+        );
+        assert_eq!(post_info.pays_fee, Pays::No);
+    })
+}
+
+#[test]
+fn eval_vote_state_refunds_unexecuted_proposal() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+
+        // Threshold not reached, proposal weight is refunded but the caller still pays
+        let post_info = Bridge::eval_vote_state(
+            Origin::signed(1),
+            prop_id,
+            src_id,
+            Box::new(proposal.clone()),
+        )
+        .unwrap();
+        assert_eq!(
+            post_info.actual_weight,
+            Some(<Test as Config>::WeightInfo::eval_vote_state(
+                Bridge::relayer_count()
+            ))
+        );
+        assert_eq!(post_info.pays_fee, Pays::Yes);
+    })
+}
+
+#[test]
+fn proposal_call_stored_once() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = Bridge::proposal_hash(&proposal);
+
+        // Votes on the same proposal don't store the call again
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_ok!(Bridge::reject_proposal(
+            Origin::signed(RELAYER_B),
+            1,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(
+            Bridge::proposal_call(prop_hash),
+            Some((proposal.clone(), 1))
+        );
+
+        // A second proposal with the same call references the stored call
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            2,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(
+            Bridge::proposal_call(prop_hash),
+            Some((proposal.clone(), 2))
+        );
+        assert!(Bridge::votes(src_id, (1, prop_hash)).is_some());
+        assert!(Bridge::votes(src_id, (2, prop_hash)).is_some());
+    })
+}
+
+#[test]
+fn migrate_to_v2_removes_call_keyed_votes() {
+    use frame_support::storage::migration::{get_storage_value, put_storage_value};
+    use migrations::deprecated::ProposalVotes as OldProposalVotes;
+    use sp_core::hashing::blake2_256;
+
+    new_test_ext().execute_with(|| {
+        let src_id: ChainId = 1;
+        let nonce: DepositNonce = 1;
+        let proposal = make_proposal(vec![10]);
+        let old_votes = OldProposalVotes::<u64, u64> {
+            votes_for: vec![RELAYER_A],
+            votes_against: vec![],
+            status: ProposalStatus::Initiated,
+            expiry: ProposalLifetime::get(),
+        };
+
+        // Insert a proposal using the old double map layout
+        let key = [
+            blake2_256(&src_id.encode()),
+            blake2_256(&(nonce, proposal).encode()),
+        ]
+        .concat();
+        put_storage_value(b"ChainBridge", b"Votes", &key, old_votes.clone());
+        assert_eq!(
+            get_storage_value::<OldProposalVotes<u64, u64>>(b"ChainBridge", b"Votes", &key),
+            Some(old_votes)
+        );
+        assert_eq!(Bridge::storage_version(), Releases::V1);
+
+        migrations::migrate_to_v2::<Test>();
+
+        assert_eq!(
+            get_storage_value::<OldProposalVotes<u64, u64>>(b"ChainBridge", b"Votes", &key),
+            None
+        );
+        assert_eq!(Bridge::storage_version(), Releases::V2);
+
+        // Migration only runs once
+        assert_eq!(migrations::migrate_to_v2::<Test>(), 0);
+    })
+}
+
+#[test]
+fn migrate_to_v2_keeps_completed_votes() {
+    use migrations::deprecated::ProposalVotes as OldProposalVotes;
+
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let executed = make_proposal(vec![10]);
+        let pending = make_proposal(vec![11]);
+        let old_votes = |status| OldProposalVotes::<u64, u64> {
+            votes_for: vec![RELAYER_A, RELAYER_B],
+            votes_against: vec![],
+            status,
+            expiry: ProposalLifetime::get(),
+        };
+        Votes::<Test>::insert(
+            src_id,
+            (1, executed.clone()),
+            old_votes(ProposalStatus::Approved),
+        );
+        Votes::<Test>::insert(
+            src_id,
+            (2, pending.clone()),
+            old_votes(ProposalStatus::Initiated),
+        );
+
+        Votes::<Test>::insert(
+            src_id,
+            (3, make_proposal(vec![12])),
+            old_votes(ProposalStatus::Rejected),
+        );
+
+        migrations::migrate_to_v2::<Test>();
+
+        assert_eq!(
+            Bridge::legacy_votes(src_id, (1, executed.clone())),
+            Some(old_votes(ProposalStatus::Approved))
+        );
+        assert_eq!(Bridge::legacy_votes(src_id, (2, pending.clone())), None);
+        assert!(Bridge::legacy_votes_remain());
+        assert_events(vec![Event::bridge(RawEvent::LegacyProposalsDropped(1))]);
+
+        // The proposal executed before the migration can't be executed again
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                1,
+                src_id,
+                r_id,
+                Box::new(executed.clone())
+            ),
+            Error::<Test>::ProposalAlreadyComplete
+        );
+        assert_eq!(Bridge::proposal_votes(src_id, 1, &executed), None);
+
+        // The dropped pending proposal is voted on again
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            2,
+            src_id,
+            r_id,
+            Box::new(pending.clone())
+        ));
+        assert!(Bridge::proposal_votes(src_id, 2, &pending).is_some());
+
+        // Only the admin can remove the legacy entries
+        assert_noop!(
+            Bridge::purge_legacy_votes(Origin::signed(RELAYER_A), 1),
+            DispatchError::BadOrigin
+        );
+
+        // The flag stays set until every entry is removed
+        assert_ok!(Bridge::purge_legacy_votes(Origin::root(), 1));
+        assert!(Bridge::legacy_votes_remain());
+        assert_ok!(Bridge::purge_legacy_votes(Origin::root(), 5));
+        assert!(!Bridge::legacy_votes_remain());
+        assert_eq!(Bridge::legacy_votes(src_id, (1, executed.clone())), None);
+        assert_events(vec![
+            Event::bridge(RawEvent::LegacyVotesPurged(1)),
+            Event::bridge(RawEvent::LegacyVotesPurged(1)),
+        ]);
+
+        // The executed proposal is no longer checked against the legacy map
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            src_id,
+            r_id,
+            Box::new(executed.clone())
+        ));
+    })
+}
+
+#[test]
+fn expired_proposal_pruned() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = Bridge::proposal_hash(&proposal);
+        let prune_at = ProposalLifetime::get() + 1 + ProposalRetention::get();
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(
+            Bridge::prune_queue(prune_at),
+            vec![(src_id, prop_id, prop_hash)]
+        );
+
+        // Retention period hasn't passed yet
+        Bridge::on_initialize(prune_at - 1);
+        assert!(Bridge::votes(src_id, (prop_id, prop_hash)).is_some());
+
+        Bridge::on_initialize(prune_at);
+        assert_eq!(Bridge::votes(src_id, (prop_id, prop_hash)), None);
+        assert_eq!(Bridge::proposal_call(prop_hash), None);
+        assert!(Bridge::prune_queue(prune_at).is_empty());
+
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A)),
+            Event::bridge(RawEvent::ProposalExpired(src_id, prop_id)),
+        ]);
+    })
+}
+
+#[test]
+fn completed_proposal_pruned() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = Bridge::proposal_hash(&proposal);
+        let prune_at = ProposalLifetime::get() + 1 + ProposalRetention::get();
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+
+        Bridge::on_initialize(prune_at);
+        assert_eq!(Bridge::votes(src_id, (prop_id, prop_hash)), None);
+        assert_eq!(Bridge::proposal_call(prop_hash), None);
+
+        // Completed proposals don't signal expiry
+        assert_events(vec![
+            Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, prop_id)),
+        ]);
+    })
+}
+
+#[test]
+fn clean_proposal() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        let prop_hash = Bridge::proposal_hash(&proposal);
+
+        assert_noop!(
+            Bridge::clean_proposal(Origin::signed(1), src_id, prop_id, prop_hash),
+            Error::<Test>::ProposalDoesNotExist
+        );
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+
+        // Expired, but still within the retention period
+        System::set_block_number(ProposalLifetime::get() + 1);
+        assert_noop!(
+            Bridge::clean_proposal(Origin::signed(1), src_id, prop_id, prop_hash),
+            Error::<Test>::ProposalNotPrunable
+        );
+
+        System::set_block_number(ProposalLifetime::get() + 1 + ProposalRetention::get());
+        let post_info =
+            Bridge::clean_proposal(Origin::signed(1), src_id, prop_id, prop_hash).unwrap();
+        assert_eq!(post_info.pays_fee, Pays::No);
+        assert_eq!(Bridge::votes(src_id, (prop_id, prop_hash)), None);
+        assert_eq!(Bridge::proposal_call(prop_hash), None);
+
+        assert_events(vec![Event::bridge(RawEvent::ProposalExpired(
+            src_id, prop_id,
+        ))]);
+    })
+}
+
+#[test]
+fn deposit_nonce_executed_once() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        let other_proposal = make_proposal(vec![11]);
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(
+            Bridge::executed_nonce(src_id, prop_id),
+            Some(Bridge::proposal_hash(&proposal))
+        );
+
+        // A different call can't be voted through with the same deposit nonce
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(other_proposal.clone())
+            ),
+            Error::<Test>::NonceAlreadyExecuted
+        );
+        assert_eq!(
+            Bridge::votes(src_id, (prop_id, Bridge::proposal_hash(&other_proposal))),
+            None
+        );
+
+        // The same nonce can still be used by another source chain
+        assert_eq!(Bridge::executed_nonce(src_id + 1, prop_id), None);
+    })
+}
+
+fn make_transfer_proposal(to: u64, amount: u64) -> mock::Call {
+    Call::Balances(balances::Call::transfer(to, amount))
+}
+
+#[test]
+fn failed_proposal_recorded() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"transfer");
+
+    new_test_ext_initialized(src_id, r_id, b"Balances.transfer".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        // The bridge account can't cover the transfer
+        let proposal = make_transfer_proposal(RELAYER_A, ENDOWED_BALANCE * 2);
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+
+        let prop = Bridge::votes(src_id, (prop_id, Bridge::proposal_hash(&proposal))).unwrap();
+        assert_eq!(prop.status, ProposalStatus::Failed);
+        assert_eq!(Balances::free_balance(RELAYER_A), 0);
+
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A)),
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_B)),
+            Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalFailed(
+                src_id,
+                prop_id,
+                balances::Error::<Test>::InsufficientBalance.into(),
+            )),
+        ]);
+    })
+}
+
+#[test]
+fn retry_failed_proposal() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"transfer");
+
+    new_test_ext_initialized(src_id, r_id, b"Balances.transfer".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let amount = ENDOWED_BALANCE * 2;
+        let proposal = make_transfer_proposal(RELAYER_A, amount);
+        let prop_hash = Bridge::proposal_hash(&proposal);
+
+        // Only failed proposals can be retried
+        assert_noop!(
+            Bridge::retry_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ProposalDoesNotExist
+        );
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_noop!(
+            Bridge::retry_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ProposalNotFailed
+        );
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(
+            Bridge::votes(src_id, (prop_id, prop_hash)).unwrap().status,
+            ProposalStatus::Failed
+        );
+
+        // Only relayers may retry
+        assert_noop!(
+            Bridge::retry_proposal(
+                Origin::signed(1),
+                prop_id,
+                src_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::MustBeRelayer
+        );
+
+        // Retry still fails
+        assert_ok!(Bridge::retry_proposal(
+            Origin::signed(RELAYER_C),
+            prop_id,
+            src_id,
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(Bridge::proposal_retries(src_id, prop_id), 1);
+        assert_eq!(
+            Bridge::votes(src_id, (prop_id, prop_hash)).unwrap().status,
+            ProposalStatus::Failed
+        );
+
+        // Retry succeeds once the bridge is funded
+        Balances::make_free_balance_be(&Bridge::account_id(), amount * 2);
+        assert_ok!(Bridge::retry_proposal(
+            Origin::signed(RELAYER_C),
+            prop_id,
+            src_id,
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(Bridge::proposal_retries(src_id, prop_id), 2);
+        assert_eq!(
+            Bridge::votes(src_id, (prop_id, prop_hash)).unwrap().status,
+            ProposalStatus::Executed
+        );
+        assert_eq!(Balances::free_balance(RELAYER_A), amount);
+
+        assert_events(vec![
+            Event::balances(balances::Event::Transfer(
+                Bridge::account_id(),
+                RELAYER_A,
+                amount,
+            )),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, prop_id)),
+        ]);
+    })
+}
+
+#[test]
+fn retry_limit_reached() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"transfer");
+
+    new_test_ext_initialized(src_id, r_id, b"Balances.transfer".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_transfer_proposal(RELAYER_A, ENDOWED_BALANCE * 2);
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+
+        for _ in 0..MaxProposalRetries::get() {
+            assert_ok!(Bridge::retry_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                Box::new(proposal.clone())
+            ));
+        }
+        assert_noop!(
+            Bridge::retry_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::RetryLimitReached
+        );
+    })
+}
+
+#[test]
+fn pause_bridge() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+
+        // Only the pause origin or admin may pause, only admin may unpause
+        assert_noop!(
+            Bridge::pause_bridge(Origin::signed(RELAYER_A)),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Bridge::pause_bridge(Origin::signed(One::get())));
+        assert!(Bridge::bridge_paused());
+
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::BridgePaused
+        );
+        assert_noop!(
+            Bridge::reject_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::BridgePaused
+        );
+        assert_noop!(
+            Bridge::eval_vote_state(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::BridgePaused
+        );
+        assert_noop!(
+            Bridge::transfer_fungible(DEPOSITOR, src_id, r_id, vec![1], U256::one()),
+            Error::<Test>::BridgePaused
+        );
+        assert_noop!(
+            Bridge::transfer_nonfungible(DEPOSITOR, src_id, r_id, vec![1], vec![1], vec![]),
+            Error::<Test>::BridgePaused
+        );
+        assert_noop!(
+            Bridge::transfer_generic(DEPOSITOR, src_id, r_id, vec![1]),
+            Error::<Test>::BridgePaused
+        );
+
+        assert_noop!(
+            Bridge::unpause_bridge(Origin::signed(One::get())),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(Bridge::unpause_bridge(Origin::root()));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+
+        assert_events(vec![
+            Event::bridge(RawEvent::BridgePaused),
+            Event::bridge(RawEvent::BridgeUnpaused),
+            Event::bridge(RawEvent::VoteFor(src_id, prop_id, RELAYER_A)),
+        ]);
+    })
+}
+
+#[test]
+fn pause_chain() {
+    let src_id = 1;
+    let other_id = 2;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), other_id));
+        assert_noop!(
+            Bridge::pause_chain(Origin::root(), 3),
+            Error::<Test>::ChainNotWhitelisted
+        );
+
+        assert_ok!(Bridge::pause_chain(Origin::signed(One::get()), src_id));
+        assert!(Bridge::is_paused(src_id));
+        assert!(!Bridge::is_paused(other_id));
+
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                1,
+                src_id,
+                r_id,
+                Box::new(make_proposal(vec![10]))
+            ),
+            Error::<Test>::BridgePaused
+        );
+        assert_noop!(
+            Bridge::transfer_generic(DEPOSITOR, src_id, r_id, vec![1]),
+            Error::<Test>::BridgePaused
+        );
+        // Other chains are unaffected
+        assert_ok!(Bridge::transfer_generic(DEPOSITOR, other_id, r_id, vec![1]));
+
+        assert_ok!(Bridge::unpause_chain(Origin::root(), src_id));
+        assert_ok!(Bridge::transfer_generic(DEPOSITOR, src_id, r_id, vec![1]));
+
+        assert_events(vec![
+            Event::bridge(RawEvent::ChainPaused(src_id)),
+            Event::bridge(RawEvent::GenericTransfer(
+                other_id,
+                1,
+                r_id,
+                vec![1],
+                DEPOSITOR,
+                payload_hash(r_id, Deposit::Generic { metadata: vec![1] }),
+                0,
+            )),
+            Event::bridge(RawEvent::ChainUnpaused(src_id)),
+            Event::bridge(RawEvent::GenericTransfer(
+                src_id,
+                1,
+                r_id,
+                vec![1],
+                DEPOSITOR,
+                payload_hash(r_id, Deposit::Generic { metadata: vec![1] }),
+                0,
+            )),
+        ]);
+    })
+}
+
+#[test]
+fn remove_chain() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+
+        assert_ok!(Bridge::transfer_generic(DEPOSITOR, src_id, r_id, vec![1]));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+
+        assert_ok!(Bridge::remove_chain(Origin::root(), src_id));
+        assert!(!Bridge::chain_whitelisted(src_id));
+        assert_noop!(
+            Bridge::remove_chain(Origin::root(), src_id),
+            Error::<Test>::ChainNotWhitelisted
+        );
+
+        // Pending proposals can't be completed
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ChainNotWhitelisted
+        );
+        assert_noop!(
+            Bridge::eval_vote_state(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                src_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ChainNotWhitelisted
+        );
+        assert_noop!(
+            Bridge::transfer_generic(DEPOSITOR, src_id, r_id, vec![1]),
+            Error::<Test>::ChainNotWhitelisted
+        );
+
+        // Whitelisting again continues with the previous nonce
+        System::set_block_number(2);
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), src_id));
+        assert_eq!(Bridge::chains(src_id), Some(1));
+        assert_ok!(Bridge::transfer_generic(DEPOSITOR, src_id, r_id, vec![1]));
+
+        // Proposals made before the removal stay refused
+        assert_eq!(Bridge::chain_removed_at(src_id), Some(1));
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ProposalFromRemovedChain
+        );
+        assert_ok!(Bridge::set_threshold(Origin::root(), 1));
+        assert_noop!(
+            Bridge::eval_vote_state(
+                Origin::signed(RELAYER_B),
+                prop_id,
+                src_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ProposalFromRemovedChain
+        );
+        assert_eq!(
+            Bridge::proposal_votes(src_id, prop_id, &proposal)
+                .unwrap()
+                .status,
+            ProposalStatus::Initiated
+        );
+
+        assert_events(vec![
+            Event::bridge(RawEvent::ChainRemoved(src_id)),
+            Event::bridge(RawEvent::ChainWhitelisted(src_id)),
+            Event::bridge(RawEvent::GenericTransfer(
+                src_id,
+                2,
+                r_id,
+                vec![1],
+                DEPOSITOR,
+                payload_hash(r_id, Deposit::Generic { metadata: vec![1] }),
+                0,
+            )),
+            Event::bridge(RawEvent::RelayerThresholdChanged(1)),
+        ]);
+
+        // New proposals for the chain can be voted on
+        let other = make_proposal(vec![11]);
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id + 1,
+            src_id,
+            r_id,
+            Box::new(other.clone())
+        ));
+        assert_eq!(
+            Bridge::proposal_votes(src_id, prop_id + 1, &other)
+                .unwrap()
+                .status,
+            ProposalStatus::Executed
+        );
+    })
+}
+
+#[test]
+fn set_chain_direction() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let proposal = make_proposal(vec![10]);
+        assert_eq!(Bridge::chain_direction(src_id), ChainDirection::Both);
+        assert_noop!(
+            Bridge::set_chain_direction(Origin::root(), 3, ChainDirection::Inbound),
+            Error::<Test>::ChainNotWhitelisted
+        );
+
+        // Inbound only
+        assert_ok!(Bridge::set_chain_direction(
+            Origin::root(),
+            src_id,
+            ChainDirection::Inbound
+        ));
+        assert_noop!(
+            Bridge::transfer_generic(DEPOSITOR, src_id, r_id, vec![1]),
+            Error::<Test>::DirectionNotAllowed
+        );
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            1,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+
+        // Outbound only
+        assert_ok!(Bridge::set_chain_direction(
+            Origin::root(),
+            src_id,
+            ChainDirection::Outbound
+        ));
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_B),
+                1,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::DirectionNotAllowed
+        );
+        assert_ok!(Bridge::transfer_generic(DEPOSITOR, src_id, r_id, vec![1]));
+
+        assert_events(vec![
+            Event::bridge(RawEvent::ChainDirectionChanged(
+                src_id,
+                ChainDirection::Outbound,
+            )),
+            Event::bridge(RawEvent::GenericTransfer(
+                src_id,
+                1,
+                r_id,
+                vec![1],
+                DEPOSITOR,
+                payload_hash(r_id, Deposit::Generic { metadata: vec![1] }),
+                0,
+            )),
+        ]);
+    })
+}
+
+#[test]
+fn proposal_resource_checks() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+        let info = Bridge::resources(r_id).unwrap();
+
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                [9; 32],
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ResourceDoesNotExist
+        );
+
+        // Disabled resources can't be voted on
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            r_id,
+            ResourceInfo {
+                enabled: false,
+                ..info.clone()
+            }
+        ));
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ResourceDisabled
+        );
+
+        // Resources restricted to other chains can't be voted on
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            r_id,
+            ResourceInfo {
+                allowed_chains: vec![src_id + 1],
+                ..info.clone()
+            }
+        ));
+        assert_noop!(
+            Bridge::reject_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::ResourceChainNotAllowed
+        );
+
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            r_id,
+            ResourceInfo {
+                allowed_chains: vec![src_id],
+                ..info
+            }
+        ));
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+    })
+}
+
+#[test]
+fn migrate_to_v3_converts_resources() {
+    use frame_support::storage::migration::{get_storage_value, put_storage_value};
+    use sp_core::hashing::blake2_256;
+
+    new_test_ext().execute_with(|| {
+        let r_id = derive_resource_id(1, b"remark");
+        let method = b"System.remark".to_vec();
+
+        // Insert a resource using the old layout
+        let key = blake2_256(&r_id.encode());
+        put_storage_value(b"ChainBridge", b"Resources", &key, method.clone());
+        assert_eq!(
+            get_storage_value::<Vec<u8>>(b"ChainBridge", b"Resources", &key),
+            Some(method.clone())
+        );
+
+        // Requires the previous migration
+        assert_eq!(migrations::migrate_to_v3::<Test>(), 0);
+        migrations::migrate_to_v2::<Test>();
+        migrations::migrate_to_v3::<Test>();
+
+        assert_eq!(
+            get_storage_value::<ResourceInfo>(b"ChainBridge", b"Resources", &key),
+            Some(ResourceInfo::new(ResourceKind::Generic, method))
+        );
+        assert_eq!(Bridge::storage_version(), Releases::V3);
+        assert_eq!(migrations::migrate_to_v3::<Test>(), 0);
+    })
+}
+
+#[test]
+fn proposal_must_match_handler() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_transfer_proposal(RELAYER_A, 10);
+
+        assert_noop!(
+            Bridge::acknowledge_proposal(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                r_id,
+                Box::new(proposal.clone())
+            ),
+            Error::<Test>::InvalidProposal
+        );
+
+        let info = Bridge::resources(r_id).unwrap();
+        assert!(!MatchHandler::validate(r_id, &info, &proposal));
+        assert!(MatchHandler::validate(
+            r_id,
+            &info,
+            &make_proposal(vec![10])
+        ));
+        assert!(<() as ProposalValidator<Call>>::validate(
+            r_id, &info, &proposal
+        ));
+    })
+}
+
+#[test]
+fn parse_deposit_data() {
+    let deposits = vec![
+        (
+            ResourceKind::Fungible,
+            Deposit::Fungible {
+                amount: U256::from(100),
+                recipient: vec![1, 2, 3],
+            },
+        ),
+        (
+            ResourceKind::NonFungible,
+            Deposit::NonFungible {
+                token_id: U256::from(7),
+                recipient: vec![1, 2, 3],
+                metadata: vec![4; 40],
+            },
+        ),
+        (ResourceKind::Generic, Deposit::Generic { metadata: vec![] }),
+    ];
+
+    for (kind, deposit) in deposits {
+        let data = deposit.to_data();
+        assert_eq!(Deposit::parse(kind, &data), Some(deposit));
+
+        // Truncated or trailing bytes are rejected
+        assert_eq!(Deposit::parse(kind, &data[..data.len() - 1]), None);
+        assert_eq!(
+            Deposit::parse(kind, &[data.clone(), vec![0]].concat()),
+            None
+        );
+    }
+
+    // Length exceeds the remaining data
+    let mut data = Deposit::Generic {
+        metadata: vec![1, 2],
+    }
+    .to_data();
+    data[31] = 3;
+    assert_eq!(Deposit::parse(ResourceKind::Generic, &data), None);
+}
+
+#[test]
+fn execute_deposit() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"token");
+
+    new_test_ext_initialized(src_id, r_id, b"Token.deposit".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let amount = 10;
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            r_id,
+            ResourceInfo::new(ResourceKind::Fungible, b"Token.deposit".to_vec())
+        ));
+        let data = Deposit::Fungible {
+            amount: U256::from(amount),
+            recipient: RELAYER_A.encode(),
+        }
+        .to_data();
+        let prop_hash = Bridge::deposit_hash(r_id, &data);
+
+        // Data must match the kind of the resource
+        assert_noop!(
+            Bridge::acknowledge_deposit(
+                Origin::signed(RELAYER_A),
+                prop_id,
+                src_id,
+                r_id,
+                Deposit::Generic { metadata: vec![] }.to_data()
+            ),
+            Error::<Test>::InvalidDepositData
+        );
+
+        assert_ok!(Bridge::acknowledge_deposit(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            data.clone()
+        ));
+        assert_ok!(Bridge::reject_deposit(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            data.clone()
+        ));
+        let post_info = Bridge::acknowledge_deposit(
+            Origin::signed(RELAYER_C),
+            prop_id,
+            src_id,
+            r_id,
+            data.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            post_info.actual_weight,
+            Some(<() as WeightInfo>::acknowledge_deposit(3) + mock::HANDLER_WEIGHT)
+        );
+
+        let prop = Bridge::votes(src_id, (prop_id, prop_hash)).unwrap();
+        assert_eq!(prop.votes_for, slots(&[RELAYER_A, RELAYER_C]));
+        assert_eq!(prop.votes_against, slots(&[RELAYER_B]));
+        assert_eq!(prop.status, ProposalStatus::Executed);
+        assert_eq!(Balances::free_balance(RELAYER_A), amount);
+        assert_eq!(
+            Balances::free_balance(Bridge::account_id()),
+            ENDOWED_BALANCE - amount
+        );
+
+        assert_events(vec![
+            Event::balances(balances::Event::Transfer(
+                Bridge::account_id(),
+                RELAYER_A,
+                amount,
+            )),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, prop_id)),
+        ]);
+    })
+}
+
+#[test]
+fn reject_deposit() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let data = Deposit::Generic {
+            metadata: vec![1, 2, 3],
+        }
+        .to_data();
+
+        assert_ok!(Bridge::reject_deposit(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            data.clone()
+        ));
+        assert_ok!(Bridge::reject_deposit(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            data.clone()
+        ));
+
+        let prop = Bridge::votes(src_id, (prop_id, Bridge::deposit_hash(r_id, &data))).unwrap();
+        assert_eq!(prop.status, ProposalStatus::Rejected);
+        assert_noop!(
+            Bridge::acknowledge_deposit(
+                Origin::signed(RELAYER_C),
+                prop_id,
+                src_id,
+                r_id,
+                data.clone()
+            ),
+            Error::<Test>::ProposalAlreadyComplete
+        );
+
+        assert_events(vec![
+            Event::bridge(RawEvent::VoteAgainst(src_id, prop_id, RELAYER_A)),
+            Event::bridge(RawEvent::VoteAgainst(src_id, prop_id, RELAYER_B)),
+            Event::bridge(RawEvent::ProposalRejected(src_id, prop_id)),
+        ]);
+    })
+}
+
+#[test]
+fn failed_deposit_is_rolled_back() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"nft");
+
+    new_test_ext_initialized(src_id, r_id, b"Nft.mint".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            r_id,
+            ResourceInfo::new(ResourceKind::NonFungible, b"Nft.mint".to_vec())
+        ));
+        let token_id = U256::from(7);
+        // The handler mints the token before failing on the missing metadata
+        let data = Deposit::NonFungible {
+            token_id,
+            recipient: RELAYER_A.encode(),
+            metadata: vec![],
+        }
+        .to_data();
+
+        for relayer in vec![RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_deposit(
+                Origin::signed(relayer),
+                prop_id,
+                src_id,
+                r_id,
+                data.clone()
+            ));
+        }
+
+        let prop = Bridge::votes(src_id, (prop_id, Bridge::deposit_hash(r_id, &data))).unwrap();
+        assert_eq!(prop.status, ProposalStatus::Failed);
+        assert_eq!(
+            frame_support::storage::unhashed::get::<Vec<u8>>(&token_id.encode()),
+            None
+        );
+        assert_events(vec![
+            Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalFailed(
+                src_id,
+                prop_id,
+                DispatchError::Other("Missing metadata"),
+            )),
+        ]);
+    })
+}
+
+#[test]
+fn retry_failed_deposit() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"token");
+
+    new_test_ext_initialized(src_id, r_id, b"Token.deposit".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let amount = ENDOWED_BALANCE * 2;
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            r_id,
+            ResourceInfo::new(ResourceKind::Fungible, b"Token.deposit".to_vec())
+        ));
+        let data = Deposit::Fungible {
+            amount: U256::from(amount),
+            recipient: RELAYER_A.encode(),
+        }
+        .to_data();
+        let prop_hash = Bridge::deposit_hash(r_id, &data);
+
+        // The bridge account can't cover the deposit
+        for relayer in vec![RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_deposit(
+                Origin::signed(relayer),
+                prop_id,
+                src_id,
+                r_id,
+                data.clone()
+            ));
+        }
+        assert_eq!(
+            Bridge::votes(src_id, (prop_id, prop_hash)).unwrap().status,
+            ProposalStatus::Failed
+        );
+
+        assert_noop!(
+            Bridge::retry_deposit(Origin::signed(1), prop_id, src_id, r_id, data.clone()),
+            Error::<Test>::MustBeRelayer
+        );
+        // The deposit is identified by its data
+        assert_noop!(
+            Bridge::retry_deposit(
+                Origin::signed(RELAYER_C),
+                prop_id,
+                src_id,
+                r_id,
+                Deposit::Generic { metadata: vec![] }.to_data()
+            ),
+            Error::<Test>::ProposalDoesNotExist
+        );
+
+        // Retry succeeds once the bridge is funded
+        Balances::make_free_balance_be(&Bridge::account_id(), amount * 2);
+        let post_info = Bridge::retry_deposit(
+            Origin::signed(RELAYER_C),
+            prop_id,
+            src_id,
+            r_id,
+            data.clone(),
+        )
+        .unwrap();
+        assert_eq!(
+            post_info.actual_weight,
+            Some(<() as WeightInfo>::retry_deposit() + mock::HANDLER_WEIGHT)
+        );
+        assert_eq!(Bridge::proposal_retries(src_id, prop_id), 1);
+        assert_eq!(
+            Bridge::votes(src_id, (prop_id, prop_hash)).unwrap().status,
+            ProposalStatus::Executed
+        );
+        assert_eq!(Balances::free_balance(RELAYER_A), amount);
+        assert_noop!(
+            Bridge::retry_deposit(Origin::signed(RELAYER_C), prop_id, src_id, r_id, data),
+            Error::<Test>::ProposalNotFailed
+        );
+
+        assert_events(vec![
+            Event::balances(balances::Event::Transfer(
+                Bridge::account_id(),
+                RELAYER_A,
+                amount,
+            )),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, prop_id)),
+        ]);
+    })
+}
+
+#[test]
+fn eval_deposit_state() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let data = Deposit::Generic {
+            metadata: vec![1, 2, 3],
+        }
+        .to_data();
+        let prop_hash = Bridge::deposit_hash(r_id, &data);
+
+        assert_noop!(
+            Bridge::eval_deposit_state(Origin::signed(1), prop_id, src_id, r_id, data.clone()),
+            Error::<Test>::ProposalDoesNotExist
+        );
+        assert_ok!(Bridge::acknowledge_deposit(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            data.clone()
+        ));
+
+        // Not enough votes yet
+        assert_ok!(Bridge::eval_deposit_state(
+            Origin::signed(1),
+            prop_id,
+            src_id,
+            r_id,
+            data.clone()
+        ));
+        assert_eq!(
+            Bridge::votes(src_id, (prop_id, prop_hash)).unwrap().status,
+            ProposalStatus::Initiated
+        );
+
+        // The pending deposit executes once the threshold is lowered
+        assert_ok!(Bridge::set_threshold(Origin::root(), 1));
+        assert_ok!(Bridge::eval_deposit_state(
+            Origin::signed(1),
+            prop_id,
+            src_id,
+            r_id,
+            data.clone()
+        ));
+        assert_eq!(
+            Bridge::votes(src_id, (prop_id, prop_hash)).unwrap().status,
+            ProposalStatus::Executed
+        );
+        assert_noop!(
+            Bridge::eval_deposit_state(Origin::signed(1), prop_id, src_id, r_id, data),
+            Error::<Test>::ProposalAlreadyComplete
+        );
+
+        assert_events(vec![
+            Event::bridge(RawEvent::RelayerThresholdChanged(1)),
+            Event::bridge(RawEvent::ProposalApproved(src_id, prop_id)),
+            Event::bridge(RawEvent::ProposalSucceeded(src_id, prop_id)),
+        ]);
+    })
+}
+
+#[test]
+fn genesis_config() {
+    let r_id = derive_resource_id(1, b"remark");
+    let info = ResourceInfo::new(ResourceKind::Generic, b"System.remark".to_vec());
+
+    mock::new_test_ext_with_genesis(GenesisConfig {
+        relayers: vec![RELAYER_A, RELAYER_B, RELAYER_C],
+        threshold: TEST_THRESHOLD,
+        chains: vec![1, 2],
+        resources: vec![(r_id, info.clone())],
+    })
+    .execute_with(|| {
+        assert!(Bridge::is_relayer(&RELAYER_A));
+        assert!(Bridge::is_relayer(&RELAYER_C));
+        assert_eq!(Bridge::relayer_count(), 3);
+        assert_eq!(Bridge::relayer_threshold(), TEST_THRESHOLD);
+        assert!(Bridge::chain_whitelisted(1));
+        assert!(Bridge::chain_whitelisted(2));
+        assert_eq!(Bridge::chains(2), Some(0));
+        assert_eq!(Bridge::resources(r_id), Some(info));
+        assert_eq!(Bridge::storage_version(), Releases::V3);
+
+        let proposal = make_proposal(vec![10]);
+        for relayer in vec![RELAYER_A, RELAYER_B] {
+            assert_ok!(Bridge::acknowledge_proposal(
+                Origin::signed(relayer),
+                1,
+                1,
+                r_id,
+                Box::new(proposal.clone())
+            ));
+        }
+        assert_events(vec![Event::bridge(RawEvent::ProposalSucceeded(1, 1))]);
+    });
+
+    // The default configuration leaves the bridge unconfigured
+    mock::new_test_ext_with_genesis(Default::default()).execute_with(|| {
+        assert_eq!(Bridge::relayer_count(), 0);
+        assert_eq!(Bridge::relayer_threshold(), 1);
+    });
+}
+
+#[test]
+#[should_panic(expected = "Relayer threshold exceeds the number of relayers")]
+fn genesis_threshold_exceeds_relayers() {
+    mock::new_test_ext_with_genesis(GenesisConfig {
+        relayers: vec![RELAYER_A, RELAYER_B],
+        threshold: 3,
+        chains: vec![],
+        resources: vec![],
+    });
+}
+
+#[test]
+#[should_panic(expected = "Relayer threshold cannot be 0")]
+fn genesis_threshold_not_set() {
+    mock::new_test_ext_with_genesis(GenesisConfig {
+        relayers: vec![RELAYER_A],
+        threshold: 0,
+        chains: vec![],
+        resources: vec![],
+    });
+}
+
+#[test]
+#[should_panic(expected = "Too many relayers in genesis")]
+fn genesis_too_many_relayers() {
+    mock::new_test_ext_with_genesis(GenesisConfig {
+        relayers: (0..MaxRelayers::get() as u64 + 1).collect(),
+        threshold: 1,
+        chains: vec![],
+        resources: vec![],
+    });
+}
+
+#[test]
+fn query_proposal_votes() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        let prop_id = 1;
+        let proposal = make_proposal(vec![10]);
+
+        assert_eq!(Bridge::proposal_votes(src_id, prop_id, &proposal), None);
+        assert!(Bridge::can_vote(&RELAYER_A, src_id, prop_id, &proposal));
+        assert!(!Bridge::can_vote(&1, src_id, prop_id, &proposal));
+        assert!(!Bridge::can_vote(&RELAYER_A, 2, prop_id, &proposal));
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_A),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(
+            Bridge::proposal_votes(src_id, prop_id, &proposal)
+                .unwrap()
+                .votes_for,
+            vec![RELAYER_A]
+        );
+        assert!(!Bridge::can_vote(&RELAYER_A, src_id, prop_id, &proposal));
+        assert!(Bridge::can_vote(&RELAYER_B, src_id, prop_id, &proposal));
+
+        assert_ok!(Bridge::acknowledge_proposal(
+            Origin::signed(RELAYER_B),
+            prop_id,
+            src_id,
+            r_id,
+            Box::new(proposal.clone())
+        ));
+        assert_eq!(
+            Bridge::proposal_votes(src_id, prop_id, &proposal)
+                .unwrap()
+                .status,
+            ProposalStatus::Executed
+        );
+        assert!(!Bridge::can_vote(&RELAYER_C, src_id, prop_id, &proposal));
+        // Other proposals for an executed nonce can't be voted on either
+        assert!(!Bridge::can_vote(
+            &RELAYER_C,
+            src_id,
+            prop_id,
+            &make_proposal(vec![11])
+        ));
+    })
+}
+
+#[test]
+fn migrate_to_v4_makes_maps_enumerable() {
+    use frame_support::storage::migration::{get_storage_value, put_storage_value};
+    use sp_core::hashing::blake2_256;
+
+    new_test_ext().execute_with(|| {
+        let r_id = derive_resource_id(1, b"remark");
+        let unknown_id = derive_resource_id(1, b"unknown");
+        let info = ResourceInfo::new(ResourceKind::Generic, b"System.remark".to_vec());
+
+        // Insert entries using the old, opaque layout
+        let put = |item: &[u8], key: Vec<u8>, value: Vec<u8>| {
+            put_storage_value(b"ChainBridge", item, &blake2_256(&key), value);
+        };
+        for relayer in vec![RELAYER_A, RELAYER_B, RELAYER_C] {
+            put(b"Relayers", relayer.encode(), true.encode());
+        }
+        RelayerCount::put(3);
+        put(b"ChainNonces", 1u8.encode(), 5u64.encode());
+        put(b"ChainNonces", 200u8.encode(), 0u64.encode());
+        put(b"Resources", r_id.encode(), info.encode());
+        put(b"Resources", unknown_id.encode(), info.encode());
+        StorageVersion::put(Releases::V3);
+
+        // RELAYER_C is missing from the legacy relayers of the runtime
+        migrations::migrate_to_v4::<Test>();
+
+        assert!(Bridge::is_relayer(&RELAYER_A));
+        assert!(Bridge::is_relayer(&RELAYER_B));
+        assert!(!Bridge::is_relayer(&RELAYER_C));
+        assert_eq!(Bridge::relayer_count(), 2);
+        let mut chains = Bridge::whitelisted_chains();
+        chains.sort();
+        assert_eq!(chains, vec![(1, 5), (200, 0)]);
+        assert_eq!(Bridge::resource_ids(), vec![r_id]);
+        assert_eq!(Bridge::resources(r_id), Some(info));
+        assert_eq!(
+            get_storage_value::<ResourceInfo>(
+                b"ChainBridge",
+                b"Resources",
+                &blake2_256(&unknown_id.encode())
+            ),
+            None
+        );
+        assert_eq!(Bridge::storage_version(), Releases::V4);
+        assert_eq!(migrations::migrate_to_v4::<Test>(), 0);
+    })
+}
+
+#[test]
+fn migrate_to_v5_assigns_slots() {
+    use frame_support::storage::migration::put_storage_value;
+    use frame_support::{Blake2_128Concat, StorageHasher};
+    use migrations::deprecated::ProposalVotes as OldProposalVotes;
+    use sp_core::hashing::blake2_256;
+
+    new_test_ext().execute_with(|| {
+        let src_id: ChainId = 1;
+        let nonce: DepositNonce = 1;
+        let prop_hash = Bridge::proposal_hash(&make_proposal(vec![10]));
+        let old_votes = OldProposalVotes::<u64, u64> {
+            votes_for: vec![RELAYER_A, 10],
+            votes_against: vec![RELAYER_B],
+            status: ProposalStatus::Initiated,
+            expiry: ProposalLifetime::get(),
+        };
+
+        // Insert relayers and a proposal using the old layout
+        for relayer in vec![RELAYER_A, RELAYER_B, RELAYER_C] {
+            let key = Blake2_128Concat::hash(&relayer.encode());
+            put_storage_value(b"ChainBridge", b"Relayers", &key, true);
+        }
+        RelayerCount::put(3);
+        let key = [
+            blake2_256(&src_id.encode()),
+            blake2_256(&(nonce, prop_hash).encode()),
+        ]
+        .concat();
+        put_storage_value(b"ChainBridge", b"Proposals", &key, old_votes);
+        StorageVersion::put(Releases::V4);
+
+        migrations::migrate_to_v5::<Test>();
+
+        let mut assigned: Vec<u32> = Bridge::relayer_slots()
+            .into_iter()
+            .map(|(_, slot)| slot)
+            .collect();
+        assigned.sort();
+        assert_eq!(assigned, vec![0, 1, 2]);
+        assert_eq!(
+            Bridge::occupied_slots(),
+            vec![0, 1, 2].into_iter().collect()
+        );
+
+        // The vote of the removed relayer is dropped
+        assert_eq!(
+            Bridge::votes(src_id, (nonce, prop_hash)),
+            Some(ProposalVotes {
+                votes_for: slots(&[RELAYER_A]),
+                votes_against: slots(&[RELAYER_B]),
+                status: ProposalStatus::Initiated,
+                expiry: ProposalLifetime::get(),
+            })
+        );
+        assert_eq!(Bridge::storage_version(), Releases::V5);
+        assert_eq!(migrations::migrate_to_v5::<Test>(), 0);
+    })
+}
+
+#[test]
+fn runtime_upgrade_migrates_v1_storage() {
+    use frame_support::storage::migration::put_storage_value;
+    use frame_support::traits::OnRuntimeUpgrade;
+    use sp_core::hashing::blake2_256;
+
+    new_test_ext().execute_with(|| {
+        let r_id = derive_resource_id(1, b"remark");
+        assert_eq!(Bridge::storage_version(), Releases::V1);
+        assert!(migrations::check_version::<Test>().is_err());
+
+        // Insert entries using the V1 layout
+        let put = |item: &[u8], key: Vec<u8>, value: Vec<u8>| {
+            put_storage_value(b"ChainBridge", item, &blake2_256(&key), value);
+        };
+        for relayer in vec![RELAYER_A, RELAYER_B] {
+            put(b"Relayers", relayer.encode(), true.encode());
+        }
+        RelayerCount::put(2);
+        put(b"ChainNonces", 1u8.encode(), 5u64.encode());
+        put(
+            b"Resources",
+            r_id.encode(),
+            b"System.remark".to_vec().encode(),
+        );
+
+        Bridge::on_runtime_upgrade();
+
+        assert_eq!(Bridge::storage_version(), Releases::V5);
+        assert_eq!(migrations::check_version::<Test>(), Ok(()));
+        assert!(Bridge::is_relayer(&RELAYER_A));
+        assert!(Bridge::is_relayer(&RELAYER_B));
+        assert_eq!(Bridge::relayer_count(), 2);
+        assert_eq!(Bridge::occupied_slots(), vec![0, 1].into_iter().collect());
+        assert_eq!(Bridge::whitelisted_chains(), vec![(1, 5)]);
+        assert_eq!(
+            Bridge::resources(r_id),
+            Some(ResourceInfo::new(
+                ResourceKind::Generic,
+                b"System.remark".to_vec()
+            ))
+        );
+    })
+}
+
+#[test]
+fn enumerate_bridge_state() {
+    let src_id = 1;
+    let r_id = derive_resource_id(src_id, b"remark");
+
+    new_test_ext_initialized(src_id, r_id, b"System.remark".to_vec()).execute_with(|| {
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), 2));
+        assert_ok!(Bridge::remove_chain(Origin::root(), 2));
+        assert_ok!(Bridge::remove_relayer(Origin::root(), RELAYER_B));
+
+        let mut relayers = Bridge::relayer_list();
+        relayers.sort();
+        assert_eq!(relayers, vec![RELAYER_A, RELAYER_C]);
+        assert_eq!(Bridge::whitelisted_chains(), vec![(src_id, 0)]);
+        assert_eq!(Bridge::resource_ids(), vec![r_id]);
+    })
+}
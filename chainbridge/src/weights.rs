@@ -0,0 +1,459 @@
+//! Placeholder weights for chainbridge
+//!
+//! No benchmark results have been recorded yet. Every call is charged `PLACEHOLDER_WEIGHT`,
+//! plus `PLACEHOLDER_WEIGHT` for each unit of its components, on top of its database accesses.
+//! Runtimes should generate their weights from the benchmarks in `benchmarking.rs`:
+//!
+//! ```text
+//! ./target/release/node benchmark --chain=dev --steps=50 --repeat=20 \
+//!     --pallet=chainbridge --extrinsic='*' --execution=wasm --wasm-execution=compiled \
+//!     --heap-pages=4096 --output=./chainbridge/src/weights.rs
+//! ```
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Execution time charged for each call and each unit of its components until the weights are
+/// benchmarked. It is set well above the time of any call on reference hardware.
+pub const PLACEHOLDER_WEIGHT: Weight = 100_000_000;
+
+/// Weight functions needed for chainbridge.
+pub trait WeightInfo {
+    fn set_threshold() -> Weight;
+    fn set_threshold_ratio() -> Weight;
+    fn set_resource() -> Weight;
+    fn remove_resource() -> Weight;
+    fn whitelist_chain() -> Weight;
+    fn remove_chain() -> Weight;
+    fn set_chain_direction() -> Weight;
+    fn set_fee() -> Weight;
+    fn withdraw_fees() -> Weight;
+    fn add_relayer(m: u32) -> Weight;
+    fn remove_relayer() -> Weight;
+    fn set_relayer_key() -> Weight;
+    fn withdraw_unbonded() -> Weight;
+    fn report_misbehaviour() -> Weight;
+    fn claim_rewards() -> Weight;
+    fn pause_bridge() -> Weight;
+    fn unpause_bridge() -> Weight;
+    fn pause_chain() -> Weight;
+    fn unpause_chain() -> Weight;
+    fn purge_legacy_votes(v: u32) -> Weight;
+    fn acknowledge_proposal(r: u32) -> Weight;
+    fn reject_proposal(r: u32) -> Weight;
+    fn acknowledge_proposals_batch(r: u32, b: u32) -> Weight;
+    fn reject_proposals_batch(r: u32, b: u32) -> Weight;
+    fn acknowledge_deposit(r: u32) -> Weight;
+    fn reject_deposit(r: u32) -> Weight;
+    fn submit_signed_proposal(s: u32) -> Weight;
+    fn eval_vote_state(r: u32) -> Weight;
+    fn eval_deposit_state(r: u32) -> Weight;
+    fn clean_proposal() -> Weight;
+    fn retry_proposal() -> Weight;
+    fn retry_deposit() -> Weight;
+    fn prune_proposals(p: u32) -> Weight;
+    fn prune_deposit_records(d: u32) -> Weight;
+}
+
+/// Weights for chainbridge using the database weights of the runtime.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    fn set_threshold() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_threshold_ratio() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_resource() -> Weight {
+        PLACEHOLDER_WEIGHT.saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn remove_resource() -> Weight {
+        PLACEHOLDER_WEIGHT.saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn whitelist_chain() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn remove_chain() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn set_chain_direction() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_fee() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn withdraw_fees() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn add_relayer(m: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(m as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(m as Weight)))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn remove_relayer() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn set_relayer_key() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn withdraw_unbonded() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn report_misbehaviour() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn claim_rewards() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn pause_bridge() -> Weight {
+        PLACEHOLDER_WEIGHT.saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn unpause_bridge() -> Weight {
+        PLACEHOLDER_WEIGHT.saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn pause_chain() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn unpause_chain() -> Weight {
+        PLACEHOLDER_WEIGHT.saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn purge_legacy_votes(v: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(v as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(v as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(v as Weight)))
+    }
+    fn acknowledge_proposal(r: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(r as Weight))
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
+    fn reject_proposal(r: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(r as Weight))
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn acknowledge_proposals_batch(r: u32, b: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(r as Weight))
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(b as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(b as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(b as Weight)))
+    }
+    fn reject_proposals_batch(r: u32, b: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(r as Weight))
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(b as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(b as Weight)))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(b as Weight)))
+    }
+    fn acknowledge_deposit(r: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(r as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
+    fn reject_deposit(r: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(r as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn submit_signed_proposal(s: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn eval_vote_state(r: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(r as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
+    fn eval_deposit_state(r: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(r as Weight))
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
+    fn clean_proposal() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn retry_proposal() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn retry_deposit() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn prune_proposals(p: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(p as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+    }
+    fn prune_deposit_records(d: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(d as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(d as Weight)))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn set_threshold() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_threshold_ratio() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_resource() -> Weight {
+        PLACEHOLDER_WEIGHT.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn remove_resource() -> Weight {
+        PLACEHOLDER_WEIGHT.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn whitelist_chain() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn remove_chain() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn set_chain_direction() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_fee() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn withdraw_fees() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn add_relayer(m: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(m as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(m as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn remove_relayer() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn set_relayer_key() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn withdraw_unbonded() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn report_misbehaviour() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn claim_rewards() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn pause_bridge() -> Weight {
+        PLACEHOLDER_WEIGHT.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn unpause_bridge() -> Weight {
+        PLACEHOLDER_WEIGHT.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn pause_chain() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn unpause_chain() -> Weight {
+        PLACEHOLDER_WEIGHT.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn purge_legacy_votes(v: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(v as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(v as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(v as Weight)))
+    }
+    fn acknowledge_proposal(r: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(r as Weight))
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
+    fn reject_proposal(r: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(r as Weight))
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn acknowledge_proposals_batch(r: u32, b: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(r as Weight))
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(b as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(b as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(b as Weight)))
+    }
+    fn reject_proposals_batch(r: u32, b: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(r as Weight))
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(b as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(b as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(b as Weight)))
+    }
+    fn acknowledge_deposit(r: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(r as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
+    fn reject_deposit(r: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(r as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn submit_signed_proposal(s: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(s as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn eval_vote_state(r: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(r as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
+    fn eval_deposit_state(r: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(r as Weight))
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(r as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(r as Weight)))
+    }
+    fn clean_proposal() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn retry_proposal() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn retry_deposit() -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn prune_proposals(p: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(p as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(p as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(p as Weight)))
+    }
+    fn prune_deposit_records(d: u32) -> Weight {
+        PLACEHOLDER_WEIGHT
+            .saturating_add(PLACEHOLDER_WEIGHT.saturating_mul(d as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(d as Weight)))
+    }
+}
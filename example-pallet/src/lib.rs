@@ -2,9 +2,13 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use chainbridge as bridge;
+use codec::Decode;
 use example_erc721 as erc721;
 use frame_support::traits::{Currency, EnsureOrigin, ExistenceRequirement::AllowDeath, Get};
-use frame_support::{decl_error, decl_event, decl_module, dispatch::DispatchResult, ensure};
+use frame_support::{
+    decl_error, decl_event, decl_module, dispatch::DispatchResult, ensure, transactional,
+    weights::Weight,
+};
 use frame_system::{self as system, ensure_signed};
 use sp_arithmetic::traits::SaturatedConversion;
 use sp_core::U256;
@@ -43,6 +47,8 @@ decl_event! {
 decl_error! {
     pub enum Error for Module<T: Config>{
         InvalidTransfer,
+        /// The deposit can't be handled for its resource
+        InvalidDeposit,
     }
 }
 
@@ -61,15 +67,17 @@ decl_module! {
         /// Transfers an arbitrary hash to a (whitelisted) destination chain.
         #[weight = 195_000_000]
         pub fn transfer_hash(origin, hash: T::Hash, dest_id: bridge::ChainId) -> DispatchResult {
-            ensure_signed(origin)?;
+            let source = ensure_signed(origin)?;
 
             let resource_id = T::HashId::get();
             let metadata: Vec<u8> = hash.as_ref().to_vec();
-            <bridge::Module<T>>::transfer_generic(dest_id, resource_id, metadata)
+            <bridge::Module<T>>::transfer_generic(source, dest_id, resource_id, metadata)
         }
 
         /// Transfers some amount of the native token to some recipient on a (whitelisted) destination chain.
+        /// The amount is reverted if the bridge fee can't be paid.
         #[weight = 195_000_000]
+        #[transactional]
         pub fn transfer_native(origin, amount: BalanceOf<T>, recipient: Vec<u8>, dest_id: bridge::ChainId) -> DispatchResult {
             let source = ensure_signed(origin)?;
             ensure!(<bridge::Module<T>>::chain_whitelisted(dest_id), Error::<T>::InvalidTransfer);
@@ -77,21 +85,23 @@ decl_module! {
             T::Currency::transfer(&source, &bridge_id, amount.into(), AllowDeath)?;
 
             let resource_id = T::NativeTokenId::get();
-            <bridge::Module<T>>::transfer_fungible(dest_id, resource_id, recipient, U256::from(amount.saturated_into::<u128>()))
+            <bridge::Module<T>>::transfer_fungible(source, dest_id, resource_id, recipient, U256::from(amount.saturated_into::<u128>()))
         }
 
         /// Transfer a non-fungible token (erc721) to a (whitelisted) destination chain.
+        /// The token is not burned if the bridge fee can't be paid.
         #[weight = 195_000_000]
+        #[transactional]
         pub fn transfer_erc721(origin, recipient: Vec<u8>, token_id: U256, dest_id: bridge::ChainId) -> DispatchResult {
             let source = ensure_signed(origin)?;
             ensure!(<bridge::Module<T>>::chain_whitelisted(dest_id), Error::<T>::InvalidTransfer);
             match <erc721::Module<T>>::tokens(&token_id) {
                 Some(token) => {
-                    <erc721::Module<T>>::burn_token(source, token_id)?;
+                    <erc721::Module<T>>::burn_token(source.clone(), token_id)?;
                     let resource_id = T::Erc721Id::get();
                     let tid: &mut [u8] = &mut[0; 32];
                     token_id.to_big_endian(tid);
-                    <bridge::Module<T>>::transfer_nonfungible(dest_id, resource_id, tid.to_vec(), recipient, token.metadata)
+                    <bridge::Module<T>>::transfer_nonfungible(source, dest_id, resource_id, tid.to_vec(), recipient, token.metadata)
                 }
                 None => Err(Error::<T>::InvalidTransfer)?
             }
@@ -126,3 +136,51 @@ decl_module! {
         }
     }
 }
+
+/// Executes deposits from other chains, as an alternative to proposing the calls above
+impl<T: Config> bridge::ResourceHandler for Module<T> {
+    fn weight(_: ResourceId, _: &[u8]) -> Weight {
+        195_000_000
+    }
+
+    fn handle(
+        r_id: ResourceId,
+        _: &bridge::ResourceInfo,
+        deposit: bridge::Deposit,
+    ) -> DispatchResult {
+        match deposit {
+            bridge::Deposit::Fungible { amount, recipient } if r_id == T::NativeTokenId::get() => {
+                let to = Self::decode_recipient(&recipient)?;
+                ensure!(
+                    amount <= U256::from(u128::max_value()),
+                    Error::<T>::InvalidDeposit
+                );
+                let amount: BalanceOf<T> = amount.low_u128().saturated_into();
+                let bridge_id = <bridge::Module<T>>::account_id();
+                <T as Config>::Currency::transfer(&bridge_id, &to, amount, AllowDeath)
+            }
+            bridge::Deposit::NonFungible {
+                token_id,
+                recipient,
+                metadata,
+            } if r_id == T::Erc721Id::get() => {
+                let to = Self::decode_recipient(&recipient)?;
+                <erc721::Module<T>>::mint_token(to, token_id, metadata)
+            }
+            bridge::Deposit::Generic { metadata } if r_id == T::HashId::get() => {
+                let hash =
+                    T::Hash::decode(&mut &metadata[..]).map_err(|_| Error::<T>::InvalidDeposit)?;
+                Self::deposit_event(RawEvent::Remark(hash));
+                Ok(())
+            }
+            _ => Err(Error::<T>::InvalidDeposit)?,
+        }
+    }
+}
+
+impl<T: Config> Module<T> {
+    /// Decodes a SCALE encoded account ID of a deposit recipient
+    fn decode_recipient(recipient: &[u8]) -> Result<T::AccountId, Error<T>> {
+        T::AccountId::decode(&mut &recipient[..]).map_err(|_| Error::<T>::InvalidDeposit)
+    }
+}
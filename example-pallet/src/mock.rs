@@ -71,14 +71,39 @@ impl pallet_balances::Config for Test {
 parameter_types! {
     pub const TestChainId: u8 = 5;
     pub const ProposalLifetime: u64 = 100;
+    pub const ProposalRetention: u64 = 10;
+    pub const MaxProposalRetries: u32 = 2;
+    pub const MaxRelayers: u32 = 5;
+    pub const DepositRecordRetention: u64 = 20;
+    pub const MaxBatchSize: u32 = 3;
+    pub const RelayerBond: u64 = 0;
+    pub const UnbondingDelay: u64 = 10;
+    pub const ProposalReward: u64 = 0;
 }
 
 impl bridge::Config for Test {
     type Event = Event;
     type AdminOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type PauseOrigin = frame_system::EnsureSignedBy<One, Self::AccountId>;
     type Proposal = Call;
     type ChainId = TestChainId;
+    type ProposalValidator = bridge::MatchHandler;
+    type ResourceHandler = Example;
+    type MaxRelayers = MaxRelayers;
     type ProposalLifetime = ProposalLifetime;
+    type ProposalRetention = ProposalRetention;
+    type MaxProposalRetries = MaxProposalRetries;
+    type DepositRecordRetention = DepositRecordRetention;
+    type MaxBatchSize = MaxBatchSize;
+    type Currency = Balances;
+    type RelayerBond = RelayerBond;
+    type UnbondingDelay = UnbondingDelay;
+    type Slash = ();
+    type ProposalReward = ProposalReward;
+    type FeeCurrency = Balances;
+    type LegacyRelayers = ();
+    type LegacyResourceIds = ();
+    type WeightInfo = ();
 }
 
 parameter_types! {
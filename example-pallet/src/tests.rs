@@ -15,6 +15,19 @@ use sp_core::{blake2_256, H256};
 
 const TEST_THRESHOLD: u32 = 2;
 
+/// Bridge slots of the given relayers
+fn slots(relayers: &[u64]) -> bridge::Bitfield {
+    relayers
+        .iter()
+        .map(|r| Bridge::relayer_slot(r).expect("must be a relayer"))
+        .collect()
+}
+
+/// Hash of the payload of an outbound transfer
+fn payload_hash(r_id: bridge::ResourceId, deposit: bridge::Deposit) -> H256 {
+    Bridge::deposit_hash(r_id, &deposit.to_data())
+}
+
 fn make_remark_proposal(hash: H256) -> Call {
     let resource_id = HashId::get();
     Call::Example(crate::Call::remark(hash, resource_id))
@@ -25,15 +38,42 @@ fn make_transfer_proposal(to: u64, amount: u64) -> Call {
     Call::Example(crate::Call::transfer(to, amount.into(), resource_id))
 }
 
+/// Registers the resources transferred by the example pallet
+fn register_resources() {
+    let resources = vec![
+        (
+            HashId::get(),
+            bridge::ResourceKind::Generic,
+            b"Example.remark".to_vec(),
+        ),
+        (
+            NativeTokenId::get(),
+            bridge::ResourceKind::Fungible,
+            b"Example.transfer".to_vec(),
+        ),
+        (
+            Erc721Id::get(),
+            bridge::ResourceKind::NonFungible,
+            b"Example.mint_erc721".to_vec(),
+        ),
+    ];
+    for (id, kind, handler) in resources {
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            id,
+            bridge::ResourceInfo::new(kind, handler)
+        ));
+    }
+}
+
 #[test]
 fn transfer_hash() {
     new_test_ext().execute_with(|| {
+        register_resources();
         let dest_chain = 0;
         let resource_id = HashId::get();
         let hash: H256 = "ABC".using_encoded(blake2_256).into();
 
-        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD,));
-
         assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_chain.clone()));
         assert_ok!(Example::transfer_hash(
             Origin::signed(1),
@@ -46,6 +86,14 @@ fn transfer_hash() {
             1,
             resource_id,
             hash.as_ref().to_vec(),
+            1,
+            payload_hash(
+                resource_id,
+                bridge::Deposit::Generic {
+                    metadata: hash.as_ref().to_vec(),
+                },
+            ),
+            0,
         ));
     })
 }
@@ -53,6 +101,7 @@ fn transfer_hash() {
 #[test]
 fn transfer_native() {
     new_test_ext().execute_with(|| {
+        register_resources();
         let dest_chain = 0;
         let resource_id = NativeTokenId::get();
         let amount: u64 = 100;
@@ -71,7 +120,79 @@ fn transfer_native() {
             1,
             resource_id,
             amount.into(),
-            recipient,
+            recipient.clone(),
+            RELAYER_A,
+            payload_hash(
+                resource_id,
+                bridge::Deposit::Fungible {
+                    amount: amount.into(),
+                    recipient,
+                },
+            ),
+            0,
+        ));
+    })
+}
+
+#[test]
+fn transfer_native_with_fee() {
+    new_test_ext().execute_with(|| {
+        register_resources();
+        let dest_chain = 0;
+        let resource_id = NativeTokenId::get();
+        let amount: u64 = 100;
+        let fee: u64 = 10;
+        let recipient = vec![99];
+        let bridge_id: u64 = Bridge::account_id();
+        let fees = Bridge::fees_account_id();
+
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), dest_chain));
+        assert_ok!(Bridge::set_fee(
+            Origin::root(),
+            dest_chain,
+            resource_id,
+            fee
+        ));
+
+        // The amount isn't transferred if the fee can't be paid
+        assert_noop!(
+            Example::transfer_native(
+                Origin::signed(RELAYER_A),
+                ENDOWED_BALANCE - 5,
+                recipient.clone(),
+                dest_chain,
+            ),
+            balances::Error::<Test>::InsufficientBalance
+        );
+
+        assert_ok!(Example::transfer_native(
+            Origin::signed(RELAYER_A),
+            amount,
+            recipient.clone(),
+            dest_chain,
+        ));
+        assert_eq!(
+            Balances::free_balance(RELAYER_A),
+            ENDOWED_BALANCE - amount - fee
+        );
+        assert_eq!(Balances::free_balance(bridge_id), ENDOWED_BALANCE + amount);
+        assert_eq!(Balances::free_balance(fees), fee);
+
+        expect_event(bridge::RawEvent::FungibleTransfer(
+            dest_chain,
+            1,
+            resource_id,
+            amount.into(),
+            recipient.clone(),
+            RELAYER_A,
+            payload_hash(
+                resource_id,
+                bridge::Deposit::Fungible {
+                    amount: amount.into(),
+                    recipient,
+                },
+            ),
+            fee,
         ));
     })
 }
@@ -79,6 +200,7 @@ fn transfer_native() {
 #[test]
 fn transfer_erc721() {
     new_test_ext().execute_with(|| {
+        register_resources();
         let dest_chain = 0;
         let resource_id = Erc721Id::get();
         let token_id: U256 = U256::from(100);
@@ -117,7 +239,17 @@ fn transfer_erc721() {
             resource_id,
             token_id_slice.to_vec(),
             recipient.clone(),
-            metadata,
+            metadata.clone(),
+            RELAYER_A,
+            payload_hash(
+                resource_id,
+                bridge::Deposit::NonFungible {
+                    token_id,
+                    recipient: recipient.clone(),
+                    metadata,
+                },
+            ),
+            0,
         ));
 
         // Ensure token no longer exists
@@ -144,11 +276,12 @@ fn execute_remark() {
         let prop_id = 1;
         let src_id = 1;
         let r_id = bridge::derive_resource_id(src_id, b"hash");
-        let resource = b"Example.remark".to_vec();
+        let resource =
+            bridge::ResourceInfo::new(bridge::ResourceKind::Generic, b"Example.remark".to_vec());
 
-        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD,));
         assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
         assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_B));
+        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD,));
         assert_ok!(Bridge::whitelist_chain(Origin::root(), src_id));
         assert_ok!(Bridge::set_resource(Origin::root(), r_id, resource));
 
@@ -265,13 +398,19 @@ fn create_sucessful_transfer_proposal() {
         let prop_id = 1;
         let src_id = 1;
         let r_id = bridge::derive_resource_id(src_id, b"transfer");
-        let resource = b"Example.transfer".to_vec();
+        let resource = bridge::ResourceInfo {
+            decimals: 12,
+            ..bridge::ResourceInfo::new(
+                bridge::ResourceKind::Fungible,
+                b"Example.transfer".to_vec(),
+            )
+        };
         let proposal = make_transfer_proposal(RELAYER_A, 10);
 
-        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD,));
         assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
         assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_B));
         assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_C));
+        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD,));
         assert_ok!(Bridge::whitelist_chain(Origin::root(), src_id));
         assert_ok!(Bridge::set_resource(Origin::root(), r_id, resource));
 
@@ -283,10 +422,10 @@ fn create_sucessful_transfer_proposal() {
             r_id,
             Box::new(proposal.clone())
         ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let prop = Bridge::votes(src_id, (prop_id, Bridge::proposal_hash(&proposal))).unwrap();
         let expected = bridge::ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![],
+            votes_for: slots(&[RELAYER_A]),
+            votes_against: bridge::Bitfield::default(),
             status: bridge::ProposalStatus::Initiated,
             expiry: ProposalLifetime::get() + 1,
         };
@@ -300,10 +439,10 @@ fn create_sucessful_transfer_proposal() {
             r_id,
             Box::new(proposal.clone())
         ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let prop = Bridge::votes(src_id, (prop_id, Bridge::proposal_hash(&proposal))).unwrap();
         let expected = bridge::ProposalVotes {
-            votes_for: vec![RELAYER_A],
-            votes_against: vec![RELAYER_B],
+            votes_for: slots(&[RELAYER_A]),
+            votes_against: slots(&[RELAYER_B]),
             status: bridge::ProposalStatus::Initiated,
             expiry: ProposalLifetime::get() + 1,
         };
@@ -317,11 +456,11 @@ fn create_sucessful_transfer_proposal() {
             r_id,
             Box::new(proposal.clone())
         ));
-        let prop = Bridge::votes(src_id, (prop_id.clone(), proposal.clone())).unwrap();
+        let prop = Bridge::votes(src_id, (prop_id, Bridge::proposal_hash(&proposal))).unwrap();
         let expected = bridge::ProposalVotes {
-            votes_for: vec![RELAYER_A, RELAYER_C],
-            votes_against: vec![RELAYER_B],
-            status: bridge::ProposalStatus::Approved,
+            votes_for: slots(&[RELAYER_A, RELAYER_C]),
+            votes_against: slots(&[RELAYER_B]),
+            status: bridge::ProposalStatus::Executed,
             expiry: ProposalLifetime::get() + 1,
         };
         assert_eq!(prop, expected);
@@ -346,3 +485,64 @@ fn create_sucessful_transfer_proposal() {
         ]);
     })
 }
+
+#[test]
+fn execute_deposits() {
+    new_test_ext().execute_with(|| {
+        let src_id = 1;
+        let amount = 10;
+        let hash: H256 = "ABC".using_encoded(blake2_256).into();
+        let native =
+            bridge::ResourceInfo::new(bridge::ResourceKind::Fungible, b"Example.transfer".to_vec());
+        let remark =
+            bridge::ResourceInfo::new(bridge::ResourceKind::Generic, b"Example.remark".to_vec());
+
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_A));
+        assert_ok!(Bridge::add_relayer(Origin::root(), RELAYER_B));
+        assert_ok!(Bridge::set_threshold(Origin::root(), TEST_THRESHOLD,));
+        assert_ok!(Bridge::whitelist_chain(Origin::root(), src_id));
+        assert_ok!(Bridge::set_resource(
+            Origin::root(),
+            NativeTokenId::get(),
+            native
+        ));
+        assert_ok!(Bridge::set_resource(Origin::root(), HashId::get(), remark));
+
+        let deposits = vec![
+            (
+                NativeTokenId::get(),
+                bridge::Deposit::Fungible {
+                    amount: amount.into(),
+                    recipient: RELAYER_C.encode(),
+                },
+            ),
+            (
+                HashId::get(),
+                bridge::Deposit::Generic {
+                    metadata: hash.encode(),
+                },
+            ),
+        ];
+        for (nonce, (r_id, deposit)) in deposits.into_iter().enumerate() {
+            for relayer in vec![RELAYER_A, RELAYER_B] {
+                assert_ok!(Bridge::acknowledge_deposit(
+                    Origin::signed(relayer),
+                    nonce as u64,
+                    src_id,
+                    r_id,
+                    deposit.to_data()
+                ));
+            }
+        }
+
+        assert_eq!(Balances::free_balance(RELAYER_C), amount);
+        assert_eq!(
+            Balances::free_balance(Bridge::account_id()),
+            ENDOWED_BALANCE - amount
+        );
+        assert_events(vec![
+            Event::example(RawEvent::Remark(hash)),
+            Event::bridge(bridge::RawEvent::ProposalSucceeded(src_id, 1)),
+        ]);
+    })
+}